@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+/// Known correct answers, read from a TOML file laid out as
+///
+/// ```toml
+/// [day4.part1]
+/// "inputs/day4.txt" = 2454
+/// "example.txt" = 18
+/// ```
+///
+/// where the keys are the input names exactly as they are given to the runner.
+#[derive(Debug,PartialEq,Default)]
+pub struct Answers {
+    expected: HashMap<(usize, usize, String), String>,
+}
+
+fn parse_number(key: &str, prefix: &str) -> Result<usize, String> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse::<usize>().ok())
+        .ok_or(format!("Expected a key like {}N, found {}", prefix, key))
+}
+
+impl Answers {
+    pub fn parse(content: &str) -> Result<Answers, Box<dyn Error>> {
+        let table: toml::Table = content.parse()?;
+        let mut expected = HashMap::new();
+
+        for (day_key, parts) in &table {
+            let day = parse_number(day_key, "day")?;
+            let parts = parts.as_table()
+                .ok_or(format!("Expected a table for {}", day_key))?;
+
+            for (part_key, inputs) in parts {
+                let part = parse_number(part_key, "part")?;
+                let inputs = inputs.as_table()
+                    .ok_or(format!("Expected a table for {}.{}", day_key, part_key))?;
+
+                for (input, value) in inputs {
+                    let answer = match value {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Integer(i) => i.to_string(),
+                        _ => return Err(format!("Unsupported answer for {}.{}.{}: {}",
+                                                day_key, part_key, input, value).into()),
+                    };
+                    expected.insert((day, part, input.clone()), answer);
+                }
+            }
+        }
+
+        Ok(Answers { expected })
+    }
+
+    pub fn read(path: &str) -> Result<Answers, Box<dyn Error>> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Can't read {}: {}", path, err))?;
+
+        Answers::parse(&content)
+    }
+
+    pub fn expected(&self, day: usize, part: usize, input: &str) -> Option<&str> {
+        self.expected
+            .get(&(day, part, input.to_string()))
+            .map(|answer| answer.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("\
+[day4.part1]
+\"inputs/day4.txt\" = 2454
+\"example.txt\" = 18
+
+[day4]
+part2 = { \"example.txt\" = \"9\" }
+").unwrap();
+
+        assert_eq!(answers.expected(4, 1, "inputs/day4.txt"), Some("2454"));
+        assert_eq!(answers.expected(4, 1, "example.txt"), Some("18"));
+        assert_eq!(answers.expected(4, 2, "example.txt"), Some("9"));
+        assert_eq!(answers.expected(4, 2, "inputs/day4.txt"), None);
+        assert_eq!(answers.expected(5, 1, "example.txt"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("[day4.part1\n").is_err());
+        assert!(Answers::parse("[week4.part1]\n\"a\" = 1\n").is_err());
+        assert!(Answers::parse("[day4.one]\n\"a\" = 1\n").is_err());
+        assert!(Answers::parse("[day4]\npart1 = 3\n").is_err());
+        assert!(Answers::parse("[day4.part1]\n\"a\" = 1.5\n").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use config::Part;
+use serde_json::json;
+
+use crate::registry;
+
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        samples.sort();
+
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+
+        Stats { min: samples[0], median, mean }
+    }
+}
+
+/// Runs `f` `warmup` times without looking at the clock, then times `runs` further calls.
+pub fn measure<F: FnMut()>(warmup: usize, runs: usize, mut f: F) -> Stats {
+    for _ in 0..warmup {
+        f();
+    }
+
+    let mut samples: Vec<Duration> = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(&mut samples)
+}
+
+#[derive(Debug,PartialEq,Clone)]
+pub struct BenchResult {
+    pub day: usize,
+    pub input: String,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+impl BenchResult {
+    pub fn key(&self) -> (usize, String, String) {
+        (self.day, self.input.clone(), self.phase.clone())
+    }
+
+    pub fn to_json(&self) -> String {
+        json!({
+            "day": self.day,
+            "input": self.input,
+            "phase": self.phase,
+            "min_ns": self.stats.min.as_nanos() as u64,
+            "median_ns": self.stats.median.as_nanos() as u64,
+            "mean_ns": self.stats.mean.as_nanos() as u64,
+        }).to_string()
+    }
+
+    pub fn to_text(&self) -> String {
+        format!("  {:<6} min {:>12?}  median {:>12?}  mean {:>12?}",
+                self.phase, self.stats.min, self.stats.median, self.stats.mean)
+    }
+}
+
+/// Benchmarks parsing and each selected part of one input separately.
+pub fn bench_input(entry: &registry::Day, input: &str, content: &str, part: Part,
+                   warmup: usize, runs: usize) -> Result<Vec<BenchResult>, Box<dyn Error>> {
+    let solver = entry.solver;
+    let parsed = solver.parse(content)?;
+
+    let result = |phase: &str, stats: Stats| BenchResult {
+        day: entry.day,
+        input: input.to_string(),
+        phase: phase.to_string(),
+        stats,
+    };
+
+    let mut results = vec![result("parse", measure(warmup, runs, || {
+        black_box(solver.parse(black_box(content)).ok());
+    }))];
+
+    if part.includes_one() {
+        results.push(result("part1", measure(warmup, runs, || {
+            black_box(solver.part1(black_box(parsed.as_ref())));
+        })));
+    }
+    if part.includes_two() {
+        results.push(result("part2", measure(warmup, runs, || {
+            black_box(solver.part2(black_box(parsed.as_ref())));
+        })));
+    }
+
+    Ok(results)
+}
+
+/// Median timings of an earlier run, stored as one JSON object per line.
+#[derive(Debug,PartialEq,Default)]
+pub struct Baseline {
+    medians: HashMap<(usize, String, String), Duration>,
+}
+
+impl Baseline {
+    pub fn parse(content: &str) -> Result<Baseline, Box<dyn Error>> {
+        let mut medians = HashMap::new();
+
+        for (i, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let value: serde_json::Value = serde_json::from_str(line)
+                .map_err(|err| format!("Invalid baseline line {}: {}", i + 1, err))?;
+
+            let day = value["day"].as_u64();
+            let input = value["input"].as_str();
+            let phase = value["phase"].as_str();
+            let median = value["median_ns"].as_u64();
+
+            match (day, input, phase, median) {
+                (Some(day), Some(input), Some(phase), Some(median)) => {
+                    medians.insert(
+                        (day as usize, input.to_string(), phase.to_string()),
+                        Duration::from_nanos(median));
+                },
+                _ => return Err(format!("Incomplete baseline line {}: {}", i + 1, line).into()),
+            }
+        }
+
+        Ok(Baseline { medians })
+    }
+
+    pub fn read(path: &str) -> Result<Baseline, Box<dyn Error>> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Can't read {}: {}", path, err))?;
+
+        Baseline::parse(&content)
+    }
+
+    pub fn write(path: &str, results: &[BenchResult]) -> Result<(), Box<dyn Error>> {
+        let content: String = results.iter()
+            .map(|result| result.to_json() + "\n")
+            .collect();
+
+        fs::write(path, content)
+            .map_err(|err| format!("Can't write {}: {}", path, err))?;
+        Ok(())
+    }
+
+    pub fn median(&self, result: &BenchResult) -> Option<Duration> {
+        self.medians.get(&result.key()).copied()
+    }
+
+    /// The relative slowdown of `result` if its median is more than `threshold` percent
+    /// above the baseline median.
+    pub fn regression(&self, result: &BenchResult, threshold: f64) -> Option<f64> {
+        let baseline = self.median(result)?.as_secs_f64();
+        if baseline == 0.0 {
+            return None;
+        }
+
+        let change = (result.stats.median.as_secs_f64() - baseline) / baseline * 100.0;
+        if change > threshold { Some(change) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(phase: &str, median_ms: u64) -> BenchResult {
+        let median = Duration::from_millis(median_ms);
+        BenchResult {
+            day: 4,
+            input: "inputs/day4.txt".to_string(),
+            phase: phase.to_string(),
+            stats: Stats { min: median, median, mean: median },
+        }
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let mut odd = [3, 1, 2].map(Duration::from_millis);
+        assert_eq!(Stats::from_samples(&mut odd), Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(2),
+            mean: Duration::from_millis(2),
+        });
+
+        let mut even = [4, 1, 2, 9].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut even);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn test_measure_counts_runs() {
+        let mut calls = 0;
+        measure(2, 5, || calls += 1);
+
+        assert_eq!(calls, 7);
+    }
+
+    #[test]
+    fn test_bench_input() {
+        let entry = registry::find(1).unwrap();
+        let results = bench_input(entry, "example", "3   4\n4   3\n", Part::One, 0, 2).unwrap();
+
+        let phases: Vec<&str> = results.iter().map(|result| result.phase.as_str()).collect();
+        assert_eq!(phases, vec!["parse", "part1"]);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let value: serde_json::Value = serde_json::from_str(&result("part1", 2).to_json()).unwrap();
+
+        assert_eq!(value, json!({
+            "day": 4,
+            "input": "inputs/day4.txt",
+            "phase": "part1",
+            "min_ns": 2_000_000,
+            "median_ns": 2_000_000,
+            "mean_ns": 2_000_000,
+        }));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let results = [result("parse", 1), result("part1", 20)];
+        let content: String = results.iter().map(|result| result.to_json() + "\n").collect();
+
+        let baseline = Baseline::parse(&content).unwrap();
+        assert_eq!(baseline.median(&results[1]), Some(Duration::from_millis(20)));
+        assert_eq!(baseline.median(&result("part2", 20)), None);
+    }
+
+    #[test]
+    fn test_regression() {
+        let baseline = Baseline::parse(&(result("part1", 100).to_json() + "\n")).unwrap();
+
+        assert_eq!(baseline.regression(&result("part1", 105), 10.0), None);
+        assert_eq!(baseline.regression(&result("part1", 80), 10.0), None);
+        let change = baseline.regression(&result("part1", 150), 10.0).unwrap();
+        assert!((change - 50.0).abs() < 1e-9);
+        assert_eq!(baseline.regression(&result("part2", 150), 10.0), None);
+    }
+
+    #[test]
+    fn test_baseline_errors() {
+        assert!(Baseline::parse("{not json}").is_err());
+        assert!(Baseline::parse("{\"day\":1}").is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use std::error::Error;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use config::{Command, Config, Format, Input, Part};
+use logger::info;
+
+use answers::Answers;
+use bench::{Baseline, BenchResult};
+use report::PartReport;
+
+pub mod answers;
+pub mod bench;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
+
+pub fn input_path(input_dir: &str, day: usize) -> String {
+    Path::new(input_dir)
+        .join(format!("day{}.txt", day))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Resolves the requested days against the registry, where no days at all means every day.
+pub fn selected_days(config: &Config) -> Result<Vec<&'static registry::Day>, String> {
+    if config.days.is_empty() {
+        return Ok(registry::days().iter().collect());
+    }
+
+    config.days.iter()
+        .map(|&day| registry::find(day).ok_or(format!("Day {} is not registered", day)))
+        .collect()
+}
+
+/// The inputs given on the command line, or the day's default input file.
+pub fn selected_inputs(config: &Config, day: usize) -> Vec<Input> {
+    if config.inputs.is_empty() {
+        vec![Input::File(input_path(&config.input_dir, day))]
+    } else {
+        config.inputs.clone()
+    }
+}
+
+/// The directory holding the runner and the day crates: `--root`, or else the current
+/// directory or its parent, so that `new` works from the root as well as from `aoc/`.
+pub fn scaffold_root(config: &Config) -> Result<PathBuf, Box<dyn Error>> {
+    let holds_runner = |dir: &Path| dir.join("aoc").join("Cargo.toml").is_file();
+
+    if let Some(root) = &config.root {
+        let root = PathBuf::from(root);
+        if !holds_runner(&root) {
+            return Err(format!("{} doesn't hold the aoc runner", root.display()).into());
+        }
+        return Ok(root);
+    }
+
+    let current = env::current_dir()?;
+    current.ancestors()
+        .take(2)
+        .find(|dir| holds_runner(dir))
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("Can't find the aoc runner from {}, pass --root", current.display()).into())
+}
+
+/// Parses `content` once and solves the selected parts, timing each step.
+pub fn solve(entry: &registry::Day, input: &str, content: &str, part: Part)
+        -> Result<Vec<PartReport>, Box<dyn Error>> {
+    let start = Instant::now();
+    let parsed = entry.solver.parse(content)?;
+    let parse_time = start.elapsed();
+
+    let mut reports = Vec::new();
+    for (number, included) in [(1, part.includes_one()), (2, part.includes_two())] {
+        if !included { continue; }
+
+        let start = Instant::now();
+        let answer = if number == 1 {
+            entry.solver.part1(parsed.as_ref())
+        } else {
+            entry.solver.part2(parsed.as_ref())
+        };
+        let solve_time = start.elapsed();
+
+        reports.push(PartReport {
+            day: entry.day,
+            part: number,
+            input: input.to_string(),
+            answer,
+            expected: None,
+            parse_time,
+            solve_time,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Solves one input and prints its answers, returning how many of them did not match `answers`.
+fn run_input(config: &Config, answers: &Answers, entry: &registry::Day, input: &Input)
+        -> Result<usize, Box<dyn Error>> {
+    info!("Reading day {} input from {}", entry.day, input.name());
+
+    let content = input.read()
+        .map_err(|err| format!("Can't read {}: {}", input.name(), err))?;
+    let mut reports = solve(entry, input.name(), &content, config.part)?;
+    for report in reports.iter_mut() {
+        report.expected = answers.expected(report.day, report.part, &report.input)
+            .map(|expected| expected.to_string());
+    }
+
+    let failures = reports.iter()
+        .filter(|report| report.is_correct() == Some(false))
+        .count();
+
+    match config.format {
+        Format::Text => {
+            println!("Day {} ({}):", entry.day, input.name());
+            for report in reports {
+                println!("{}", report.to_text(config.verbosity > 0));
+            }
+        },
+        Format::Json => {
+            for report in reports {
+                println!("{}", report.to_json());
+            }
+        },
+    }
+
+    Ok(failures)
+}
+
+/// Benchmarks one input and prints the timings, returning how many regressed against `baseline`.
+fn bench_and_report(config: &Config, baseline: &Baseline, entry: &registry::Day, input: &Input,
+               results: &mut Vec<BenchResult>) -> Result<usize, Box<dyn Error>> {
+    let content = input.read()
+        .map_err(|err| format!("Can't read {}: {}", input.name(), err))?;
+    let measured = bench::bench_input(entry, input.name(), &content, config.part,
+                                      config.warmup, config.runs)?;
+
+    if config.format == Format::Text {
+        println!("Day {} ({}):", entry.day, input.name());
+    }
+
+    let mut regressions = 0;
+    for result in measured {
+        let regression = baseline.regression(&result, config.threshold);
+        if regression.is_some() {
+            regressions += 1;
+        }
+
+        match config.format {
+            Format::Text => match (regression, baseline.median(&result)) {
+                (Some(change), Some(median)) =>
+                    println!("{} [REGRESSION +{:.1}% vs {:?}]", result.to_text(), change, median),
+                _ => println!("{}", result.to_text()),
+            },
+            Format::Json => println!("{}", result.to_json()),
+        }
+        results.push(result);
+    }
+
+    Ok(regressions)
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    match config.command {
+        Command::Run => {
+            let answers = match &config.answers {
+                Some(path) => Answers::read(path)?,
+                None => Answers::default(),
+            };
+
+            let mut failures = 0;
+            for entry in selected_days(&config)? {
+                for input in selected_inputs(&config, entry.day) {
+                    failures += run_input(&config, &answers, entry, &input)?;
+                }
+            }
+
+            if failures > 0 {
+                return Err(format!("{} answer(s) did not match the expected ones", failures).into());
+            }
+        },
+        Command::Bench => {
+            let baseline = match &config.baseline {
+                Some(path) => Baseline::read(path)?,
+                None => Baseline::default(),
+            };
+
+            let mut results = Vec::new();
+            let mut regressions = 0;
+            for entry in selected_days(&config)? {
+                for input in selected_inputs(&config, entry.day) {
+                    regressions += bench_and_report(&config, &baseline, entry, &input, &mut results)?;
+                }
+            }
+
+            if let Some(path) = &config.save_baseline {
+                Baseline::write(path, &results)?;
+            }
+
+            if regressions > 0 {
+                return Err(format!("{} measurement(s) regressed by more than {}%",
+                                   regressions, config.threshold).into());
+            }
+        },
+        Command::New => {
+            let day = *config.days.first()
+                .ok_or_else(|| aoc_error::Error::invalid_argument("new needs a day"))?;
+
+            for path in scaffold::new_day(&scaffold_root(&config)?, day)? {
+                println!("Wrote {}", path.display());
+            }
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(input_path("inputs", 4), "inputs/day4.txt");
+    }
+
+    #[test]
+    fn test_selected_days() {
+        let all = selected_days(&Config::default()).unwrap();
+        assert_eq!(all.len(), registry::days().len());
+
+        let config = Config { days: vec![5, 2], ..Config::default() };
+        let days: Vec<usize> = selected_days(&config).unwrap().iter().map(|entry| entry.day).collect();
+        assert_eq!(days, vec![5, 2]);
+
+        let config = Config { days: vec![42], ..Config::default() };
+        assert!(selected_days(&config).is_err());
+    }
+
+    #[test]
+    fn test_scaffold_root() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let config = Config { root: Some(workspace.to_string_lossy().into_owned()), ..Config::default() };
+        assert_eq!(scaffold_root(&config).unwrap(), workspace);
+
+        let config = Config { root: Some(env!("CARGO_MANIFEST_DIR").to_string()), ..Config::default() };
+        assert!(scaffold_root(&config).is_err());
+    }
+
+    #[test]
+    fn test_new_without_day() {
+        let config = Config { command: Command::New, ..Config::default() };
+        let err = run(config).unwrap_err();
+
+        assert!(matches!(err.downcast_ref::<aoc_error::Error>(), Some(aoc_error::Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_solve() {
+        let entry = registry::find(1).unwrap();
+        let content = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        let reports = solve(entry, "example", content, Part::Both).unwrap();
+        let answers: Vec<(usize, Option<&str>)> = reports.iter()
+            .map(|report| (report.part, report.answer.as_deref()))
+            .collect();
+        assert_eq!(answers, vec![(1, Some("11")), (2, Some("31"))]);
+
+        let reports = solve(entry, "example", content, Part::Two).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].part, 2);
+        assert_eq!(reports[0].input, "example");
+    }
+
+    #[test]
+    fn test_selected_inputs() {
+        let config = Config { input_dir: "data".to_string(), ..Config::default() };
+        assert_eq!(selected_inputs(&config, 3), vec![Input::File("data/day3.txt".to_string())]);
+
+        let config = Config { inputs: vec![Input::Stdin, Input::parse("a.txt")], ..Config::default() };
+        assert_eq!(selected_inputs(&config, 3), vec![Input::Stdin, Input::File("a.txt".to_string())]);
+    }
+}
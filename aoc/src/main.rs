@@ -0,0 +1,26 @@
+use std::process;
+use std::env;
+
+use config::Config;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        eprintln!("{}", config::USAGE);
+        process::exit(1);
+    });
+
+    if config.help {
+        println!("{}", config::USAGE);
+        return;
+    }
+
+    logger::set_level(logger::Level::from_verbosity(config.verbosity));
+
+    if let Err(err) = aoc::run(config) {
+        eprintln!("Application error: {err}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,53 @@
+use solution::{Erased, Solver};
+
+use aoc_2024_day1::Day1;
+use aoc_2024_day2::Day2;
+use aoc_2024_day3::Day3;
+use aoc_2024_day4::Day4;
+use aoc_2024_day5::Day5;
+use aoc_2024_day6::Day6;
+
+pub struct Day {
+    pub day: usize,
+    pub solver: &'static dyn Solver,
+}
+
+// `aoc new` keeps the imports and the entries below sorted by day when it registers a new one.
+static DAYS: &[Day] = &[
+    Day { day: 1, solver: &Erased::<Day1>::new() },
+    Day { day: 2, solver: &Erased::<Day2>::new() },
+    Day { day: 3, solver: &Erased::<Day3>::new() },
+    Day { day: 4, solver: &Erased::<Day4>::new() },
+    Day { day: 5, solver: &Erased::<Day5>::new() },
+    Day { day: 6, solver: &Erased::<Day6>::new() },
+];
+
+pub fn days() -> &'static [Day] {
+    DAYS
+}
+
+pub fn find(day: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        let numbers: Vec<usize> = days().iter().map(|entry| entry.day).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(4).map(|entry| entry.day), Some(4));
+        assert!(find(0).is_none());
+        assert!(find(26).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+#[derive(Debug,PartialEq,Clone)]
+pub struct PartReport {
+    pub day: usize,
+    pub part: usize,
+    pub input: String,
+    /// `None` for a part the day hasn't solved yet.
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl PartReport {
+    /// Whether the answer matches the expected one, if there are both to compare.
+    pub fn is_correct(&self) -> Option<bool> {
+        Some(self.expected.as_ref()? == self.answer.as_ref()?)
+    }
+
+    /// One JSON object on a single line, so that a run produces JSON Lines output.
+    pub fn to_json(&self) -> String {
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "input": self.input,
+            "answer": self.answer,
+            "expected": self.expected,
+            "correct": self.is_correct(),
+            "parse_time_ns": self.parse_time.as_nanos() as u64,
+            "solve_time_ns": self.solve_time.as_nanos() as u64,
+        }).to_string()
+    }
+
+    pub fn to_text(&self, verbose: bool) -> String {
+        let mut text = format!("  Part {}: {}", self.part, self.answer.as_deref().unwrap_or("unsolved"));
+
+        match (self.is_correct(), &self.expected) {
+            (Some(true), _) => text += " [ok]",
+            (Some(false), Some(expected)) => text += &format!(" [FAIL, expected {}]", expected),
+            _ => (),
+        }
+
+        if verbose {
+            text += &format!(" (parsed in {:?}, solved in {:?})", self.parse_time, self.solve_time);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> PartReport {
+        PartReport {
+            day: 4,
+            part: 2,
+            input: "inputs/day4.txt".to_string(),
+            answer: Some("9".to_string()),
+            expected: None,
+            parse_time: Duration::from_micros(3),
+            solve_time: Duration::from_nanos(1500),
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        let parse = |json: String| serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+        assert!(!report().to_json().contains('\n'));
+        assert_eq!(parse(report().to_json()), json!({
+            "day": 4,
+            "part": 2,
+            "input": "inputs/day4.txt",
+            "answer": "9",
+            "expected": null,
+            "correct": null,
+            "parse_time_ns": 3000,
+            "solve_time_ns": 1500,
+        }));
+
+        let checked = PartReport { expected: Some("9".to_string()), ..report() };
+        let value = parse(checked.to_json());
+        assert_eq!((&value["expected"], &value["correct"]), (&json!("9"), &json!(true)));
+
+        let unsolved = PartReport { answer: None, expected: Some("9".to_string()), ..report() };
+        let value = parse(unsolved.to_json());
+        assert_eq!((&value["answer"], &value["correct"]), (&json!(null), &json!(null)));
+
+        let quoted = PartReport { input: "a \"b\"\n".to_string(), ..report() };
+        assert_eq!(parse(quoted.to_json())["input"], "a \"b\"\n");
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(report().to_text(false), "  Part 2: 9");
+        assert_eq!(report().to_text(true), "  Part 2: 9 (parsed in 3µs, solved in 1.5µs)");
+
+        let passed = PartReport { expected: Some("9".to_string()), ..report() };
+        assert_eq!(passed.to_text(false), "  Part 2: 9 [ok]");
+
+        let failed = PartReport { expected: Some("10".to_string()), ..report() };
+        assert_eq!(failed.to_text(false), "  Part 2: 9 [FAIL, expected 10]");
+
+        let unsolved = PartReport { answer: None, expected: Some("10".to_string()), ..report() };
+        assert_eq!(unsolved.to_text(false), "  Part 2: unsolved");
+    }
+
+    #[test]
+    fn test_is_correct() {
+        assert_eq!(report().is_correct(), None);
+        assert_eq!(PartReport { expected: Some("9".to_string()), ..report() }.is_correct(), Some(true));
+        assert_eq!(PartReport { expected: Some("8".to_string()), ..report() }.is_correct(), Some(false));
+        assert_eq!(PartReport { answer: None, expected: Some("8".to_string()), ..report() }.is_correct(), None);
+    }
+}
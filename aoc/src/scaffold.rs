@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST: &str = "\
+[package]
+name = \"aoc_2024_day{N}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+aoc_error = { path = \"../aoc_error\" }
+solution = { path = \"../solution\" }
+matrix = { path = \"../matrix\" }
+";
+
+const SOLUTION: &str = "\
+use aoc_error::Error;
+use solution::Solution;
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    type Input = Vec<String>;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        Ok(content.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part1(_input: &Self::Input) -> Self::Part1 {
+        None
+    }
+
+    fn part2(_input: &Self::Input) -> Self::Part2 {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = \"\\
+\";
+
+    #[test]
+    fn test_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let input = Day{N}::parse(EXAMPLE)?;
+
+        assert_eq!(Day{N}::part1(&input), None);
+        assert_eq!(Day{N}::part2(&input), None);
+        Ok(())
+    }
+}
+";
+
+pub fn crate_name(day: usize) -> String {
+    format!("aoc_2024_day{}", day)
+}
+
+pub fn manifest(day: usize) -> String {
+    MANIFEST.replace("{N}", &day.to_string())
+}
+
+pub fn solution(day: usize) -> String {
+    SOLUTION.replace("{N}", &day.to_string())
+}
+
+/// The day number at the start of `text`, if it is directly followed by `end`.
+fn day_before(text: &str, end: &str) -> Option<usize> {
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    if digits > 0 && text[digits..].starts_with(end) {
+        text[..digits].parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Inserts `line` among the lines for which `day_of` finds a day, keeping them sorted.
+fn insert_sorted<F>(text: &str, day: usize, line: &str, day_of: F) -> Result<String, String>
+        where F: Fn(&str) -> Option<usize> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let days: Vec<(usize, usize)> = lines.iter()
+        .enumerate()
+        .filter_map(|(i, line)| day_of(line).map(|day| (i, day)))
+        .collect();
+
+    if days.iter().any(|&(_, existing)| existing == day) {
+        return Err(format!("Day {} is already registered", day));
+    }
+
+    let position = match days.iter().rev().find(|&&(_, existing)| existing < day) {
+        Some(&(i, _)) => i + 1,
+        None => days.first().ok_or("No registered day to insert the new one next to")?.0,
+    };
+    lines.insert(position, line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day crate to the dependencies of the runner's manifest.
+pub fn register_dependency(manifest: &str, day: usize) -> Result<String, String> {
+    let line = format!("{} = {{ path = \"../{}\" }}", crate_name(day), crate_name(day));
+
+    insert_sorted(manifest, day, &line, |line| {
+        day_before(line.strip_prefix("aoc_2024_day")?, " =")
+    })
+}
+
+/// Adds the import and the registry entry of the day to `registry.rs`.
+pub fn register_solver(registry: &str, day: usize) -> Result<String, String> {
+    let import = format!("use {}::Day{};", crate_name(day), day);
+    let registry = insert_sorted(registry, day, &import, |line| {
+        day_before(line.strip_prefix("use aoc_2024_day")?, "::")
+    })?;
+
+    let entry = format!("    Day {{ day: {}, solver: &Erased::<Day{}>::new() }},", day, day);
+    insert_sorted(&registry, day, &entry, |line| {
+        day_before(line.trim_start().strip_prefix("Day { day: ")?, ",")
+    })
+}
+
+/// Creates the crate of a new day next to the runner and registers it, returning
+/// the files that were written.
+pub fn new_day(root: &Path, day: usize) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day {} is not an Advent of Code day", day).into());
+    }
+
+    let crate_dir = root.join(crate_name(day));
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()).into());
+    }
+
+    let runner_manifest = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("registry.rs");
+
+    let updated_manifest = register_dependency(&fs::read_to_string(&runner_manifest)?, day)?;
+    let updated_registry = register_solver(&fs::read_to_string(&registry)?, day)?;
+
+    fs::create_dir_all(crate_dir.join("src"))?;
+    let day_manifest = crate_dir.join("Cargo.toml");
+    let day_solution = crate_dir.join("src").join("lib.rs");
+    fs::write(&day_manifest, manifest(day))?;
+    fs::write(&day_solution, solution(day))?;
+
+    fs::write(&runner_manifest, updated_manifest)?;
+    fs::write(&registry, updated_registry)?;
+
+    Ok(vec![day_manifest, day_solution, runner_manifest, registry])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "\
+[dependencies]
+solution = { path = \"../solution\" }
+aoc_2024_day1 = { path = \"../aoc_2024_day1\" }
+aoc_2024_day10 = { path = \"../aoc_2024_day10\" }
+";
+
+    const REGISTRY: &str = "\
+use aoc_2024_day1::Day1;
+use aoc_2024_day10::Day10;
+
+static DAYS: &[Day] = &[
+    Day { day: 1, solver: &Erased::<Day1>::new() },
+    Day { day: 10, solver: &Erased::<Day10>::new() },
+];
+";
+
+    #[test]
+    fn test_templates() {
+        assert!(manifest(7).contains("name = \"aoc_2024_day7\""));
+        assert!(solution(7).contains("impl Solution for Day7 {"));
+        assert!(!solution(7).contains("{N}"));
+    }
+
+    #[test]
+    fn test_day_before() {
+        assert_eq!(day_before("12 = x", " ="), Some(12));
+        assert_eq!(day_before("12::Day12", " ="), None);
+        assert_eq!(day_before(" = x", " ="), None);
+    }
+
+    #[test]
+    fn test_register_dependency() {
+        let manifest = register_dependency(MANIFEST, 7).unwrap();
+
+        assert!(manifest.contains("aoc_2024_day1 = { path = \"../aoc_2024_day1\" }\n\
+                                   aoc_2024_day7 = { path = \"../aoc_2024_day7\" }\n\
+                                   aoc_2024_day10 ="));
+        assert!(register_dependency(MANIFEST, 10).is_err());
+        assert!(register_dependency("[dependencies]\n", 7).is_err());
+    }
+
+    #[test]
+    fn test_register_solver() {
+        let registry = register_solver(REGISTRY, 11).unwrap();
+
+        assert!(registry.contains("use aoc_2024_day10::Day10;\nuse aoc_2024_day11::Day11;\n"));
+        assert!(registry.contains("Erased::<Day10>::new() },\n    Day { day: 11, solver: &Erased::<Day11>::new() },\n];"));
+        assert!(register_solver(REGISTRY, 1).is_err());
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+        fs::write(root.join("aoc").join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(root.join("aoc").join("src").join("registry.rs"), REGISTRY).unwrap();
+
+        let written = new_day(&root, 3).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(root.join("aoc_2024_day3").join("src").join("lib.rs").exists());
+        assert!(fs::read_to_string(root.join("aoc").join("src").join("registry.rs")).unwrap()
+            .contains("use aoc_2024_day3::Day3;"));
+
+        assert!(new_day(&root, 3).is_err());
+        assert!(new_day(&root, 26).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,59 +1,45 @@
 use std::collections::HashMap;
-use std::fs;
-use std::error::Error;
+use aoc_error::{split_words, Error};
+use solution::Solution;
 
-pub struct Config {
-    pub file_path: String,
-}
-
-impl Config {
-    pub fn new(args: &[String]) -> Config {
-        if args.len() < 2 {
-            panic!("Need filename as first argument.");
-        }
+pub struct Day1;
 
-        let file_path = args[1].clone();
+impl Solution for Day1 {
+    type Input = (Vec<usize>, Vec<usize>);
+    type Part1 = usize;
+    type Part2 = usize;
 
-        Config { file_path }
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        parse_content(content)
     }
 
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 2 {
-            return Err("Need filename as first argument.");
-        }
-
-        Ok(Config::new(args))
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        distance(&input.0, &input.1)
     }
-}
-
-
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let content = fs::read_to_string(&config.file_path)?;
 
-    let distance = find_distance(&content)?;
-    println!("The distance is: {}", distance);
-
-    let similarity = find_similarity(&content)?;
-    println!("The similarity score is: {}", similarity);
-    Ok(())
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        similarity(&input.0, &input.1)
+    }
 }
 
-pub fn parse_content(content: &str) -> Result<(Vec<usize>, Vec<usize>), Box<dyn std::error::Error>> {
+pub fn parse_content(content: &str) -> Result<(Vec<usize>, Vec<usize>), Error> {
     let mut first_column = Vec::new();
     let mut second_column = Vec::new();
 
-    for line in content.lines() {
+    for (i, line) in content.lines().enumerate() {
         if line.trim().is_empty() {
             continue;
         }
 
-        let words: Vec<&str> = line.split_whitespace().collect();
+        let words: Vec<(usize, &str)> = split_words(line).collect();
         if words.len() != 2 {
-            return Err(format!("Invalid input line: {}", line).into());
+            return Err(Error::parse(i + 1, 1, format!("expected two numbers, found {}", words.len())));
         }
 
-        let left = words[0].parse::<usize>()?;
-        let right = words[1].parse::<usize>()?;
+        let number = |(offset, word): (usize, &str)| word.parse::<usize>()
+            .map_err(|err| Error::parse_at(line, offset, format!("{}: {}", err, word)).offset_lines(i));
+        let left = number(words[0])?;
+        let right = number(words[1])?;
 
         first_column.push(left);
         second_column.push(right);
@@ -61,16 +47,23 @@ pub fn parse_content(content: &str) -> Result<(Vec<usize>, Vec<usize>), Box<dyn
     Ok((first_column, second_column))
 }
 
-pub fn find_distance(content: &str) -> Result<usize, Box<dyn std::error::Error>> {
-    let (mut c1, mut c2) = parse_content(content)?;
+pub fn distance(left: &[usize], right: &[usize]) -> usize {
+    let mut c1 = left.to_vec();
+    let mut c2 = right.to_vec();
 
     c1.sort();
     c2.sort();
 
-    Ok(c1.iter()
+    c1.iter()
         .zip(c2.iter())
-        .map(|(a, b)| if a > b { a - b } else { b - a })
-        .sum())
+        .map(|(a, b)| a.abs_diff(*b))
+        .sum()
+}
+
+pub fn find_distance(content: &str) -> Result<usize, Error> {
+    let (c1, c2) = parse_content(content)?;
+
+    Ok(distance(&c1, &c2))
 }
 
 pub fn count_all_occurences(numbers: &[usize]) -> HashMap<usize, usize> {
@@ -81,17 +74,22 @@ pub fn count_all_occurences(numbers: &[usize]) -> HashMap<usize, usize> {
 
     counts
 }
-pub fn find_similarity(content: &str) -> Result<usize, Box<dyn std::error::Error>> {
-    let (left, right) = parse_content(content)?;
 
-    let counts_from_left = count_all_occurences(&left);
-    let counts_from_right = count_all_occurences(&right);
+pub fn similarity(left: &[usize], right: &[usize]) -> usize {
+    let counts_from_left = count_all_occurences(left);
+    let counts_from_right = count_all_occurences(right);
 
     let mut sum: usize = 0;
     for (key, left_value) in &counts_from_left {
-        sum += key*left_value*counts_from_right.get(&key).copied().unwrap_or(0);
+        sum += key*left_value*counts_from_right.get(key).copied().unwrap_or(0);
     }
-    Ok(sum)
+    sum
+}
+
+pub fn find_similarity(content: &str) -> Result<usize, Error> {
+    let (left, right) = parse_content(content)?;
+
+    Ok(similarity(&left, &right))
 }
 
 
@@ -112,7 +110,7 @@ mod tests {
         if result == 11 {
             Ok(())
         } else {
-            return Err(format!("Distance is {}, expected 11", result).into());
+            Err(format!("Distance is {}, expected 11", result).into())
         }
     }
 
@@ -129,8 +127,8 @@ mod tests {
         if  col1 == [3,4,2,1,3,3] && col2 == [4,3,5,3,9,3] {
             Ok(())
         } else {
-            return Err(format!("First column is {:?}, expected [3,4,2,1,3,3], \
-                               while second column is {:?}, expected [4,3,5,3,9,4]", col1, col2).into());
+            Err(format!("First column is {:?}, expected [3,4,2,1,3,3], \
+                               while second column is {:?}, expected [4,3,5,3,9,4]", col1, col2).into())
         }
     }
 
@@ -147,8 +145,29 @@ mod tests {
         if result == 31 {
             Ok(())
         } else {
-            return Err(format!("Similarity is {}, expected 31", result).into());
+            Err(format!("Similarity is {}, expected 31", result).into())
         }
     }
 
+    #[test]
+    fn solution_of_test_data() -> Result<(), Box<dyn std::error::Error>> {
+        let content =  "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+        let input = Day1::parse(content)?;
+        assert_eq!(Day1::part1(&input), 11);
+        assert_eq!(Day1::part2(&input), 31);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error_location() {
+        assert!(matches!(parse_content("3   4\n4   x\n"), Err(Error::Parse { line: 2, column: 5, .. })));
+        assert!(matches!(parse_content("3   4\n4   4x\n"), Err(Error::Parse { line: 2, column: 5, .. })));
+        assert!(matches!(parse_content("3   4\n\n4\n"), Err(Error::Parse { line: 3, column: 1, .. })));
+    }
 }
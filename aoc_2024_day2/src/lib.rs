@@ -1,30 +1,27 @@
-use std::error::Error;
+use aoc_error::Error;
+use solution::Solution;
 
-pub struct Config {
-    pub file_path: String,
-}
-
-impl Config {
-    pub fn new(args: &[String]) -> Config {
-        if args.len() < 2 {
-            panic!("Need filename as first argument.");
-        }
+pub struct Day2;
 
-        let file_path = args[1].clone();
+impl Solution for Day2 {
+    type Input = Vec<Vec<usize>>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        Config { file_path }
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        row_reader::parse_content_by_rows(content)
     }
 
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 2 {
-            return Err("Need filename as first argument.");
-        }
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        safe_reports(input)
+    }
 
-        Ok(Config::new(args))
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        safe_reports_with_dampener(input)
     }
 }
 
-pub fn safe_changes(row: &Vec<usize>) -> bool {
+pub fn safe_changes(row: &[usize]) -> bool {
     if row.len() < 2 { return true; }
 
     let increasing = row[0] < row[1];
@@ -41,7 +38,7 @@ pub fn safe_changes(row: &Vec<usize>) -> bool {
     result
 }
 
-pub fn remove_an_element(row: &Vec<usize>) -> Vec<Vec<usize>> {
+pub fn remove_an_element(row: &[usize]) -> Vec<Vec<usize>> {
     let mut result = Vec::new();
 
     for i in 0..row.len() {
@@ -49,12 +46,12 @@ pub fn remove_an_element(row: &Vec<usize>) -> Vec<Vec<usize>> {
             row.iter()
             .enumerate()
             .filter(|&(j,_)| j!=i)
-            .map(|(_,value)| value.clone()).collect());
+            .map(|(_,value)| *value).collect());
     }
     result
 }
 
-pub fn safe_changes_with_dampener(row: &Vec<usize>) -> bool {
+pub fn safe_changes_with_dampener(row: &[usize]) -> bool {
     if row.len() < 2 { return true; }
 
     if safe_changes(row) { return true; }
@@ -67,42 +64,30 @@ pub fn safe_changes_with_dampener(row: &Vec<usize>) -> bool {
     false
 }
 
-pub fn safe_changes_total(rows: &Vec<Vec<usize>>) -> Vec<bool> {
+pub fn safe_changes_total(rows: &[Vec<usize>]) -> Vec<bool> {
     rows.iter()
-        .map(|a| safe_changes(a))
+        .map(|row| safe_changes(row))
         .collect()
 }
 
-pub fn safe_changes_total_with_dampener(rows: &Vec<Vec<usize>>) -> Vec<bool> {
+pub fn safe_changes_total_with_dampener(rows: &[Vec<usize>]) -> Vec<bool> {
     rows.iter()
-        .map(|a| safe_changes_with_dampener(a))
+        .map(|row| safe_changes_with_dampener(row))
         .collect()
 }
 
-pub fn safe_reports(rows: &Vec<Vec<usize>>) -> usize {
+pub fn safe_reports(rows: &[Vec<usize>]) -> usize {
     let result = safe_changes_total(rows);
 
     result.iter().filter(|&&x| x).count()
 }
 
-pub fn safe_reports_with_dampener(rows: &Vec<Vec<usize>>) -> usize {
+pub fn safe_reports_with_dampener(rows: &[Vec<usize>]) -> usize {
     let result = safe_changes_total_with_dampener(rows);
 
     result.iter().filter(|&&x| x).count()
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let rows = row_reader::read_rows_from_file(
-        &config.file_path)?;
-
-    let number_of_safe_reports = safe_reports(&rows);
-    println!("Number of safe reports: {}", number_of_safe_reports);
-
-    let number_of_safe_reports_with_dampener = safe_reports_with_dampener(&rows);
-    println!("Number of safe reports with dampener: {}", number_of_safe_reports_with_dampener);
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +162,22 @@ mod tests {
         assert_eq!(safe_reports_with_dampener(&input), 4);
     }
 
+    #[test]
+    fn test_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        let input = Day2::parse(content)?;
+
+        assert_eq!(Day2::part1(&input), 2);
+        assert_eq!(Day2::part2(&input), 4);
+        Ok(())
+    }
+
     #[test]
     fn test_remove_an_element() -> Result<(), Box<dyn std::error::Error>> {
         let input: Vec<usize> = vec![7,6,4,2,1];
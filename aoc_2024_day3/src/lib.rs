@@ -1,29 +1,35 @@
-use std::fs;
-use std::error::Error;
 use regex::Regex;
 use once_cell::sync::Lazy;
 
-pub struct Config {
-    pub file_path: String,
-}
+use aoc_error::Error;
+use solution::Solution;
+use tokenizer::parser::{process_tokens, tokenize_text};
+use tokenizer::token::Token;
+use tokenizer::visitor::MultiplicationVisitor;
 
-impl Config {
-    pub fn new(args: &[String]) -> Config {
-        if args.len() < 2 {
-            panic!("Need filename as first argument.");
-        }
+pub struct Day3;
+
+/// The memory dump, kept as text for the regex of part one and as tokens for part two.
+pub struct Program {
+    pub text: String,
+    pub tokens: Vec<Box<dyn Token>>,
+}
 
-        let file_path = args[1].clone();
+impl Solution for Day3 {
+    type Input = Program;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        Config { file_path }
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        Ok(Program { text: content.to_string(), tokens: tokenize_text(content)? })
     }
 
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 2 {
-            return Err("Need filename as first argument.");
-        }
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        parse_and_eval(&input.text)
+    }
 
-        Ok(Config::new(args))
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        process_tokens(&mut MultiplicationVisitor::new(), &input.tokens)
     }
 }
 
@@ -53,17 +59,6 @@ pub fn parse_and_eval(input: &str) -> usize {
         }).sum()
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let content = fs::read_to_string(config.file_path)?;
-
-    let the_sum = parse_and_eval(&content);
-    println!("The sum of all multiplications: {the_sum}");
-
-    let the_modified_sum = tokenizer::parser::process_text(&content);
-    println!("The modified sum of all multiplications: {the_modified_sum}");
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,8 +119,17 @@ mod tests {
                      xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
         let actual: usize = 322;
 
-        let result = tokenizer::parser::process_text(input);
+        let result = tokenizer::parser::process_text(input).unwrap();
         assert_eq!(result, actual);
     }
 
+    #[test]
+    fn test_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let input = Day3::parse("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))")?;
+
+        assert_eq!(Day3::part1(&input), 161);
+        assert_eq!(Day3::part2(&input), 48);
+        Ok(())
+    }
+
 }
@@ -1,109 +1,68 @@
-use std::fs;
-use std::error::Error;
+use aoc_error::Error;
+use matrix::{Direction, Matrix, Pattern};
+use solution::Solution;
 
-use config::Config;
-use matrix::Matrix;
+pub struct Day4;
 
-pub fn count_columns(content: &str) -> usize {
-    if let Some(line) = content.lines().next() {
-        line.len()
-    } else {
-        0
-    }
-}
+impl Solution for Day4 {
+    type Input = Matrix<char>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-pub fn count_rows(content: &str) -> usize {
-    content.lines().count()
-}
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        parse_content(content)
+    }
 
-pub fn parse_content(content: &str) -> Result<Matrix<char>, Box<dyn Error>> {
-    let number_of_columns = count_columns(content);
-    let number_of_rows = count_rows(content);
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        count_words("XMAS", input)
+    }
 
-    let mut matrix = Matrix::new(number_of_rows, number_of_columns, ' ');
-    for (i, line) in content.lines().enumerate() {
-        for (j, c) in line.chars().enumerate() {
-            matrix.set(i, j, c)?;
-        }
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        count_crosses("MAS", input)
     }
+}
 
-    Ok(matrix)
+pub fn parse_content(content: &str) -> Result<Matrix<char>, Error> {
+    content.parse()
+}
+
+pub fn has_word(word: &str, matrix: &Matrix<char>, i: usize, j: usize, direction: Direction) -> bool {
+    matrix.ray(i, j, direction.delta())
+        .take(word.len())
+        .copied()
+        .eq(word.chars())
 }
 
 pub fn has_word_lr(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if j + word.len() > matrix.ncols { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i, j+k).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::Right)
 }
 
 pub fn has_word_rl(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if j + 1 < word.len() { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i, j-k).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::Left)
 }
 
 pub fn has_word_ud(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if i + word.len() > matrix.nrows { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i+k, j).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::Down)
 }
 
 pub fn has_word_du(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if i + 1 < word.len() { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i-k, j).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::Up)
 }
 
 pub fn has_word_dur(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if i + 1 < word.len() { return false; }
-    if j + word.len() > matrix.ncols { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i-k, j+k).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::UpRight)
 }
 
 pub fn has_word_ddr(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if i + word.len() > matrix.nrows { return false; }
-    if j + word.len() > matrix.ncols { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i+k, j+k).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::DownRight)
 }
 
 pub fn has_word_dul(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if i + 1 < word.len() { return false; }
-    if j + 1 < word.len() { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i-k, j-k).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::UpLeft)
 }
 
 pub fn has_word_ddl(word: &str, matrix: &Matrix<char>, i: usize, j: usize) -> bool {
-    if i + word.len() > matrix.nrows { return false; }
-    if j + 1 < word.len() { return false; }
-    for (k,c) in word.char_indices() {
-        let t = matrix.get(i+k, j-k).unwrap();
-        if *t != c { return false; }
-    }
-    true
+    has_word(word, matrix, i, j, Direction::DownLeft)
 }
 
 pub fn count_words(word: &str, matrix: &Matrix<char>) -> usize {
@@ -111,93 +70,35 @@ pub fn count_words(word: &str, matrix: &Matrix<char>) -> usize {
 
     for i in 0..matrix.nrows {
         for j in 0..matrix.ncols {
-            if has_word_lr(word, matrix, i, j) { counter += 1; }
-            if has_word_rl(word, matrix, i, j) { counter += 1; }
-            if has_word_ud(word, matrix, i, j) { counter += 1; }
-            if has_word_du(word, matrix, i, j) { counter += 1; }
-            if has_word_dur(word, matrix, i, j) { counter += 1; }
-            if has_word_ddr(word, matrix, i, j) { counter += 1; }
-            if has_word_dul(word, matrix, i, j) { counter += 1; }
-            if has_word_ddl(word, matrix, i, j) { counter += 1; }
+            counter += Direction::ALL.iter()
+                .filter(|&&direction| has_word(word, matrix, i, j, direction))
+                .count();
         }
     }
     counter
 }
-pub fn count_crosses(word: &str, matrix: &Matrix<char>) -> usize {
-    let mut counter: usize = 0;
-
-    let second_char = word.chars().skip(1).next().unwrap();
 
-    for i in 1..matrix.nrows-1 {
-        for j in 1..matrix.ncols-1 {
-            if *matrix.get(i,j).unwrap() != second_char { continue; }
+/// Two copies of `word` crossing on the diagonals of a square, both read from the top.
+pub fn cross_pattern(word: &str) -> Pattern<char> {
+    let n = word.chars().count();
+    let mut cells = Matrix::new(n, n, None);
 
-            if ( has_word_dur(word, matrix, i+1, j-1) && has_word_ddr(word, matrix, i-1, j-1)) ||
-                ( has_word_dur(word, matrix, i+1, j-1 ) && has_word_dul(word, matrix, i+1, j+1)) ||
-                ( has_word_ddl(word, matrix, i-1, j+1) && has_word_ddr(word, matrix, i-1, j-1)) ||
-                ( has_word_ddl(word, matrix, i-1, j+1) && has_word_dul(word, matrix, i+1, j+1)) {
-                counter += 1;
-            }
-
-        }
+    for (k, c) in word.chars().enumerate() {
+        cells.set(k, k, Some(c)).unwrap();
+        cells.set(k, n - 1 - k, Some(c)).unwrap();
     }
-    counter
+    Pattern::new(cells)
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let content = fs::read_to_string(config.file_path)?;
-
-    let matrix: Matrix<char> = parse_content(&content)?;
-    let number_of_words = count_words("XMAS", &matrix);
-
-    println!("Occurence of XMAS: {number_of_words}");
-
-    let number_of_crosses = count_crosses("MAS", &matrix);
-    println!("Occurence of MAS crosses: {number_of_crosses}");
-
-    Ok(())
+/// Counts the squares where both diagonals read `word`, in either direction.
+pub fn count_crosses(word: &str, matrix: &Matrix<char>) -> usize {
+    matrix.find_all_orientations(&cross_pattern(word)).len()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_count_columns() {
-        let input = "\
-MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-
-        let ncols = count_columns(input);
-        assert_eq!(10, ncols);
-    }
-
-    #[test]
-    fn test_count_rows() {
-        let input = "\
-MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-
-        let nrows = count_rows(input);
-        assert_eq!(10, nrows);
-    }
-
     #[test]
     fn test_parse_content() {
         let input = "\
@@ -222,6 +123,11 @@ MXMXAXMASX";
         assert_eq!(*c, 'A');
     }
 
+    #[test]
+    fn test_parse_ragged_content() {
+        assert!(matches!(parse_content("XMAS\nXMA\n"), Err(Error::Parse { line: 2, column: 4, .. })));
+    }
+
     #[test]
     fn test_has_word_lr() {
         let input = "\
@@ -383,6 +289,11 @@ MXMXAXMASX";
         assert!(!has_word_ddl("XMAS", &matrix, 0,0));
     }
 
+    #[test]
+    fn test_cross_pattern() {
+        assert_eq!(cross_pattern("MAS"), Pattern::parse("M.M\n.A.\nS.S", '.').unwrap());
+    }
+
     #[test]
     fn test_count_words() {
         let input = "\
@@ -402,4 +313,22 @@ MXMXAXMASX";
         assert_eq!(result, 18);
 
     }
+    #[test]
+    fn test_solution() {
+        let input = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+        let matrix = Day4::parse(input).unwrap();
+
+        assert_eq!(Day4::part1(&matrix), 18);
+        assert_eq!(Day4::part2(&matrix), 9);
+    }
 }
@@ -1,7 +1,27 @@
 use std::collections::HashSet;
-use std::fs;
-use std::error::Error;
-use config::Config;
+
+use aoc_error::Error;
+use solution::Solution;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Queue;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        parse_content(content)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        input.calculate_sum_of_valid_middle_pages()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.calculate_sum_of_reordered_middle_pages()
+    }
+}
 
 #[derive(Debug,PartialEq)]
 pub struct Update {
@@ -58,12 +78,18 @@ pub struct Queue {
     updates: Vec<Update>,
 }
 
+impl Default for Queue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Queue {
     pub fn new() -> Self {
         Self { ordering: HashSet::new(), updates: Vec::new() }
     }
 
-    pub fn calculate_sum_of_reordered_middle_pages(&mut self) -> usize {
+    pub fn calculate_sum_of_reordered_middle_pages(&self) -> usize {
         self.updates
             .iter()
             .filter(|update| {
@@ -88,37 +114,45 @@ impl Queue {
 
 }
 
-pub fn parse_ordering(content: &str) -> Result<HashSet<(usize,usize)>, Box<dyn Error>> {
+/// Parses a page number from `word`, found at byte `offset` of the line with index `i`.
+fn parse_page(i: usize, line: &str, offset: usize, word: &str) -> Result<usize, Error> {
+    word.parse::<usize>()
+        .map_err(|err| Error::parse_at(line, offset, format!("{}: {}", err, word)).offset_lines(i))
+}
+
+pub fn parse_ordering(content: &str) -> Result<HashSet<(usize,usize)>, Error> {
     let mut ordering: HashSet<(usize, usize)> = HashSet::new();
 
-    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+    for (i, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
         let words: Vec<&str> = line.split('|').collect();
 
         if words.len() != 2 {
-            return Err(format!("Line does not contain an ordering: {}", line).into());
+            return Err(Error::parse(i + 1, 1, format!("Line does not contain an ordering: {}", line)));
         }
 
         ordering.insert(
             (
-                words[0].parse::<usize>()?,
-                words[1].parse::<usize>()?)
+                parse_page(i, line, 0, words[0])?,
+                parse_page(i, line, words[0].len() + 1, words[1])?)
             );
     }
 
     Ok(ordering)
 }
 
-pub fn parse_updates(content: &str) -> Result<Vec<Update>, Box<dyn Error>> {
+pub fn parse_updates(content: &str) -> Result<Vec<Update>, Error> {
     let mut updates: Vec<Update> = Vec::new();
 
-    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+    for (i, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
         let words: Vec<&str> = line.split(',').collect();
 
         let mut pages: Vec<usize> = Vec::with_capacity(words.len());
+        let mut offset = 0;
         for word in words {
             pages.push(
-                word.parse::<usize>()?
+                parse_page(i, line, offset, word)?
             );
+            offset += word.len() + 1;
         }
 
         updates.push( Update::new(pages) );
@@ -127,34 +161,22 @@ pub fn parse_updates(content: &str) -> Result<Vec<Update>, Box<dyn Error>> {
     Ok(updates)
 }
 
-pub fn parse_content(content: &str) -> Result<Queue, Box<dyn Error>> {
-    let mut parts = content.split("\n\n");
+pub fn parse_content(content: &str) -> Result<Queue, Error> {
+    let (ordering, updates) = content.split_once("\n\n")
+        .ok_or_else(|| Error::parse(content.lines().count() + 1, 1,
+                                    "Expected an empty line between the ordering and the updates"))?;
 
-    let ordering = parse_ordering(
-        parts.next().ok_or("Unexpected content format")?
-    )?;
+    let ordering_lines = ordering.lines().count() + 1;
 
-    let updates = parse_updates(
-        parts.next().ok_or("Unexpected content format")?
-    )?;
+    let ordering = parse_ordering(ordering)?;
+
+    let updates = parse_updates(updates)
+        .map_err(|err| err.offset_lines(ordering_lines))?;
 
     Ok( Queue{ ordering, updates } )
 }
 
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let content = fs::read_to_string(config.file_path)?;
-
-    let mut queue = parse_content(&content)?;
-
-    let sum_of_valid_middles = queue.calculate_sum_of_valid_middle_pages();
-    let sum_of_reordered_middles = queue.calculate_sum_of_reordered_middle_pages();
-
-    println!("Sum of valid middles: {sum_of_valid_middles}");
-    println!("Sum of reordered  middles: {sum_of_reordered_middles}");
-
-    Ok(())
-}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,16 +253,14 @@ use std::collections::HashSet;
     }
 
     fn fill_updates() -> Vec<Update> {
-        let mut updates: Vec<Update> = Vec::new();
-
-        updates.push( Update::new([75,47,61,53,29].to_vec()));
-        updates.push( Update::new([97,61,53,29,13].to_vec()));
-        updates.push( Update::new([75,29,13      ].to_vec()));
-        updates.push( Update::new([75,97,47,61,53].to_vec()));
-        updates.push( Update::new([61,13,29      ].to_vec()));
-        updates.push( Update::new([97,13,75,29,47].to_vec()));
-
-        updates
+        vec![
+            Update::new([75,47,61,53,29].to_vec()),
+            Update::new([97,61,53,29,13].to_vec()),
+            Update::new([75,29,13      ].to_vec()),
+            Update::new([75,97,47,61,53].to_vec()),
+            Update::new([61,13,29      ].to_vec()),
+            Update::new([97,13,75,29,47].to_vec()),
+        ]
     }
 
     fn fill_content() -> Queue {
@@ -286,6 +306,20 @@ use std::collections::HashSet;
         }
     }
 
+    #[test]
+    fn test_parse_error_location() {
+        let result = parse_content("47|53\n97|x\n\n75,47");
+        assert!(matches!(result, Err(Error::Parse { line: 2, column: 4, .. })));
+
+        let result = parse_content("47|53\n97|13\n\n75,47\n\n61,,13");
+        assert!(matches!(result, Err(Error::Parse { line: 6, column: 4, .. })));
+
+        let result = parse_content("47|53\n97|97x\n\n75,47");
+        assert!(matches!(result, Err(Error::Parse { line: 2, column: 4, .. })));
+
+        assert!(parse_content("47|53\n").is_err());
+    }
+
     #[test]
     fn test_is_valid() {
         let input = fill_with_ordering();
@@ -297,4 +331,13 @@ use std::collections::HashSet;
 
     }
 
+    #[test]
+    fn test_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let input = Day5::parse(&fill_with_content())?;
+
+        assert_eq!(Day5::part1(&input), 143);
+        assert_eq!(Day5::part2(&input), 123);
+        Ok(())
+    }
+
 }
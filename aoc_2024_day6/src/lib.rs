@@ -1,48 +1,48 @@
-use std::fs;
-use std::error::Error;
+use std::io::{self, IsTerminal};
 
-use config::Config;
-use matrix::Matrix;
+use aoc_error::Error;
+use logger::{debug, trace};
+use matrix::{BitGrid, Colour, Coord, Direction, Matrix};
+use solution::Solution;
 
 static BLOCKED: char = '#';
 static START: char = '^';
-static VISITED: char = '#';
-
-#[repr(usize)]
-#[derive(PartialEq,Debug,Clone, Copy)]
-enum Orientation {
-    Up = 0,
-    Right,
-    Down,
-    Left,
-}
 
+pub struct Day6;
 
-impl Orientation {
+impl Solution for Day6 {
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = Option<usize>;
 
-    fn next(&mut self) {
-        *self = match *self {
-            Orientation::Up => Orientation::Right,
-            Orientation::Right => Orientation::Down,
-            Orientation::Down => Orientation::Left,
-            Orientation::Left => Orientation::Up,
-        };
+    fn parse(content: &str) -> Result<Self::Input, Error> {
+        Map::parse_input(content)
     }
 
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        let mut path = Path::new(input.clone());
+        path.walk_the_path();
+
+        path.count_visited_positions()
+    }
+
+    fn part2(_input: &Self::Input) -> Self::Part2 {
+        None
+    }
 }
 
-#[derive(Debug,PartialEq)]
+#[derive(Debug,PartialEq,Clone)]
 pub struct Map {
     pub rows: Vec<Vec<usize>>,
     pub cols: Vec<Vec<usize>>,
-    pub starting_position: (usize, usize),
+    pub starting_position: Coord,
 
     height: usize,
     width: usize,
 }
 
 impl Map {
-    pub fn new(rows: Vec<Vec<usize>>, cols: Vec<Vec<usize>>, starting_position: (usize, usize)) -> Self {
+    pub fn new(rows: Vec<Vec<usize>>, cols: Vec<Vec<usize>>, starting_position: Coord) -> Self {
 
         let height = rows.len();
         let width = cols.len();
@@ -50,143 +50,114 @@ impl Map {
         Map { rows, cols, starting_position, height, width }
     }
 
-    pub fn parse_input(input: &str) -> Self {
-        let nrows = input.lines()
-            .count();
+    pub fn parse_input(input: &str) -> Result<Self, Error> {
+        let grid: Matrix<char> = input.parse()?;
 
-        let ncols = input.lines()
-            .next()
-            .unwrap_or("")
-            .chars()
-            .count();
+        let mut rows: Vec<Vec<usize>> = vec![Vec::new(); grid.nrows];
+        let mut cols: Vec<Vec<usize>> = vec![Vec::new(); grid.ncols];
 
-        let mut rows: Vec<Vec<usize>> = vec![Vec::new(); nrows];
-        let mut cols: Vec<Vec<usize>> = vec![Vec::new(); ncols];
+        let mut starting_position = None;
 
-        let mut starting_position = (0,0);
+        for (i, &c) in grid.iter().enumerate() {
+            let (row, col) = (i / grid.ncols, i % grid.ncols);
 
-        for (row, line) in input.lines().enumerate() {
-            for (col, c) in line.chars().enumerate() {
-                if c == BLOCKED {
-                    rows[row].push(col);
-                    cols[col].push(row);
-                } else if c == START {
-                    starting_position = (row, col);
+            if c == BLOCKED {
+                rows[row].push(col);
+                cols[col].push(row);
+            } else if c == START {
+                if starting_position.is_some() {
+                    return Err(Error::parse(row + 1, col + 1, "The map has more than one guard"));
                 }
+                starting_position = Some(Coord::new(row, col));
             }
         }
 
-        Map::new(rows, cols, starting_position)
-    }
-
-    pub fn is_inside(&self, position: (usize, usize)) -> bool {
-        let (row, col) = position;
+        let starting_position = starting_position
+            .ok_or_else(|| Error::invalid_argument(format!("The map has no guard '{}'", START)))?;
 
-        row < self.height && col < self.width
+        Ok(Map::new(rows, cols, starting_position))
     }
-
 }
 
 #[derive(Debug,PartialEq)]
 struct Path {
     pub map: Map,
-    pub current_position: (usize, usize),
+    /// `None` once the guard has left the map.
+    pub current_position: Option<Coord>,
     pub free_space: Matrix<(usize,usize,usize,usize)>,
-    pub visited: Matrix<bool>,
-    pub orientation: Orientation,
+    pub visited: BitGrid,
+    pub orientation: Direction,
 }
 
-type WalkFn = fn(&mut Path, usize, usize);
 impl Path {
     pub fn new(map: Map) -> Path {
 
         let current_position = map.starting_position;
         let free_space = Path::setup_free_space(&map);
 
-        let mut visited: Matrix<bool> = Matrix::new(map.height, map.width, false);
-        visited.set(current_position.0, current_position.1, true).unwrap();
+        let mut visited = BitGrid::new(map.height, map.width);
+        visited.insert(current_position);
 
-        Path { map, current_position, free_space, visited, orientation: Orientation::Up }
+        Path { map, current_position: Some(current_position), free_space, visited, orientation: Direction::Up }
     }
 
     fn walk_up(&mut self, row: usize, col: usize) {
         let npos = self.free_space.get(row, col).unwrap().0;
-        println!("Walk up: {}, {}, {}", row, col, npos);
+        trace!("Walk up: {}, {}, {}", row, col, npos);
 
-        for walk in (row-npos+1..row).rev() {
-            println!("UP: walk: {}", walk);
+        for walk in (row+1-npos..row).rev() {
+            trace!("UP: walk: {}", walk);
             self.visited.set(walk, col, true).unwrap();
-            self.current_position = (walk, col);
-        }
-
-        if self.current_position.0 == 0 {
-            self.current_position.0 = self.map.height;
+            self.current_position = Some(Coord::new(walk, col));
         }
     }
 
     fn walk_right(&mut self, row: usize, col: usize) {
         let npos = self.free_space.get(row, col).unwrap().1;
-        println!("Walk right: {}, {}, {}", row, col, npos);
+        trace!("Walk right: {}, {}, {}", row, col, npos);
 
         for walk in col+1..col+npos {
             self.visited.set(row, walk, true).unwrap();
-            self.current_position = (row, walk);
-        }
-
-        if self.current_position.1 == self.map.width - 1 {
-            self.current_position.1 += 1;
+            self.current_position = Some(Coord::new(row, walk));
         }
     }
 
     fn walk_down(&mut self, row: usize, col: usize) {
         let npos = self.free_space.get(row, col).unwrap().2;
-        println!("Walk down: {}, {}, {}", row, col, npos);
+        trace!("Walk down: {}, {}, {}", row, col, npos);
 
         for walk in row+1..row+npos {
             self.visited.set(walk, col, true).unwrap();
-            self.current_position = (walk, col);
-        }
-
-        if self.current_position.0 == self.map.height - 1 {
-            self.current_position.0 += 1;
+            self.current_position = Some(Coord::new(walk, col));
         }
     }
 
     fn walk_left(&mut self, row: usize, col: usize) {
         let npos = self.free_space.get(row, col).unwrap().3;
-        println!("Walk left: {}, {}, {}", row, col, npos);
+        trace!("Walk left: {}, {}, {}", row, col, npos);
 
-        for walk in (col-npos+1..col).rev() {
+        for walk in (col+1-npos..col).rev() {
             self.visited.set(row, walk, true).unwrap();
-            self.current_position = (row, walk);
+            self.current_position = Some(Coord::new(row, walk));
         }
-
-        if self.current_position.1 == 0 {
-            self.current_position.1 = self.map.width;
-        }
-    }
-
-    fn walk_funcs() -> &'static [WalkFn] {
-        &[
-            Path::walk_up,
-            Path::walk_right,
-            Path::walk_down,
-            Path::walk_left,
-        ]
     }
 
-    fn walk(&mut self) {
-
-        let row = self.current_position.0;
-        let col = self.current_position.1;
+    fn walk(&mut self, position: Coord) {
+        let Coord { row, col } = position;
 
-        let funcs = Path::walk_funcs();
-        let index = self.orientation as usize;
+        match self.orientation {
+            Direction::Up => self.walk_up(row, col),
+            Direction::Right => self.walk_right(row, col),
+            Direction::Down => self.walk_down(row, col),
+            Direction::Left => self.walk_left(row, col),
+            diagonal => panic!("The guard never walks {:?}", diagonal),
+        }
 
-        if let Some(&walk_fn) = funcs.get(index) {
-            walk_fn(self, row, col);
-        } else {
-            panic!("Invalid Orientation index!");
+        // The free space ends at an obstruction or at the edge, and only the edge lets the guard out
+        if let Some(end) = self.current_position {
+            if self.visited.step(end, self.orientation).is_none() {
+                self.current_position = None;
+            }
         }
     }
     pub fn get_up_free_space(map: &Map) -> Matrix<usize> {
@@ -199,12 +170,11 @@ impl Path {
             next_block_idx = 0;
             for j in 0..map.rows.len() {
                 // On a blocked square
-                if next_block_idx != map.cols[i].len() {
-                    if j == map.cols[i][next_block_idx] {
-                        free_space.set(j, i, 0).unwrap();
-                        next_block_idx += 1;
-                        continue
-                    }
+                if next_block_idx != map.cols[i].len()
+                    && j == map.cols[i][next_block_idx] {
+                    free_space.set(j, i, 0).unwrap();
+                    next_block_idx += 1;
+                    continue
                 }
 
                 if next_block_idx == 0 { // Before first blocked square in column
@@ -279,12 +249,11 @@ impl Path {
             next_block_idx = 0;
             for j in 0..map.cols.len() {
                 // On a blocked square
-                if next_block_idx != map.rows[i].len() {
-                    if j == map.rows[i][next_block_idx] {
-                        free_space.set(i, j, 0).unwrap();
-                        next_block_idx += 1;
-                        continue
-                    }
+                if next_block_idx != map.rows[i].len()
+                    && j == map.rows[i][next_block_idx] {
+                    free_space.set(i, j, 0).unwrap();
+                    next_block_idx += 1;
+                    continue
                 }
                 
                 if next_block_idx == 0 { // Before first blocked square in column
@@ -306,62 +275,54 @@ impl Path {
         let down = Path::get_down_free_space(map);
         let left = Path::get_left_free_space(map);
 
-        let mut free_space = Matrix::new(
-            map.height,
-            map.width,
-            (
-                map.width,
-                map.height,
-                map.width,
-                map.height,
-            )
-        );
+        // All four come from the same map, so their shapes always agree
+        let up_right = up.zip_with(&right, |&up, &right| (up, right)).unwrap();
+        let down_left = down.zip_with(&left, |&down, &left| (down, left)).unwrap();
 
-        for i in 0..map.rows.len() {
-            for j in 0..map.cols.len() {
-                free_space.set(i, j, (
-                        *up.get(i,j).unwrap(),
-                        *right.get(i,j).unwrap(),
-                        *down.get(i,j).unwrap(),
-                        *left.get(i,j).unwrap())).unwrap();
-            }
-        }
-        free_space
+        up_right.zip_with(&down_left, |&(up, right), &(down, left)| (up, right, down, left)).unwrap()
     }
 
-    pub fn parse_input(input: &str) -> Self {
-        Path::new(Map::parse_input(input))
+    /// The map with the visited positions marked `X` and the guard drawn as an arrow,
+    /// coloured red by ANSI escapes if `ansi` is set.
+    pub fn render(&self, ansi: bool) -> String {
+        let blocks = self.map.rows.iter()
+            .enumerate()
+            .flat_map(|(row, cols)| cols.iter().map(move |&col| Coord::new(row, col)));
+        let guard = match self.orientation {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            _ => '<',
+        };
+
+        self.visited.to_matrix().render(|&visited| if visited { 'X' } else { '.' })
+            .overlay(blocks, BLOCKED)
+            .overlay(self.current_position, guard)
+            .highlight(self.current_position, Colour::Red)
+            .ansi(ansi)
+            .to_string()
     }
 
     pub fn walk_the_path(&mut self) {
-        while self.map.is_inside(self.current_position) {
-            println!("Current position, orientation, is_inside: {:?}, {:?}, {}",
-                     self.current_position, self.orientation, self.map.is_inside(self.current_position));
-            self.walk();
-            self.orientation.next();
+        while let Some(position) = self.current_position {
+            debug!("Current position, orientation: {:?}, {:?}", position, self.orientation);
+            trace!("\n{}", self.render(io::stderr().is_terminal()));
+            self.walk(position);
+            self.orientation = self.orientation.turn_right();
         }
     }
 
     pub fn count_visited_positions(&self) -> usize {
-        self.visited.iter().filter(|&&v| v).count()
+        self.visited.count_ones()
     }
 }
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let content = fs::read_to_string(config.file_path)?;
-
-    let mut path: Path = Path::parse_input(&content);
-    path.walk_the_path();
-
-    let number_of_visited_positions = path.count_visited_positions();
-    println!("Number of visited positions: {}", number_of_visited_positions);
-
-    Ok(())
-}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    static VISITED: char = 'X';
+
     fn construct_input() -> &'static str {
         "\
 ....#.....
@@ -429,7 +390,7 @@ mod tests {
                 vec![7],
                 vec![1],
             ],
-            (6,4),
+            Coord::new(6,4),
         );
         map
     }
@@ -442,47 +403,81 @@ mod tests {
     fn test_map_parse_input() {
         let input = construct_input();
 
-        let map = Map::parse_input(input);
+        let map = Map::parse_input(input).unwrap();
 
         let actual = create_map();
         println!("{:?}", map);
         assert!(map == actual);
     }
 
+    #[test]
+    fn test_map_parse_guard_errors() {
+        assert!(matches!(Map::parse_input(""), Err(Error::InvalidArgument(_))));
+        assert!(matches!(Map::parse_input("..#\n..."), Err(Error::InvalidArgument(_))));
+        assert!(matches!(Map::parse_input(".^.\n..^"), Err(Error::Parse { line: 2, column: 3, .. })));
+    }
+
     #[test]
     fn test_path_parse_input() {
         let input = construct_input();
 
-        let path = Path::parse_input(input);
+        let path = Path::new(Map::parse_input(input).unwrap());
 
         let actual = create_path();
         println!("{:?}", path);
         assert!(path == actual);
+
+        let rendered = path.render(false);
+        assert_eq!(rendered.lines().nth(6), Some(".#..^....."));
     }
 
     #[test]
     fn test_walk_the_path() {
         let input = construct_input();
-        let mut path = Path::parse_input(input);
+        let mut path = Path::new(Map::parse_input(input).unwrap());
 
         let actual: Matrix<bool> = construct_visited(10);
 
         path.walk_the_path();
 
-        assert!(actual == path.visited);
+        assert!(BitGrid::from(&actual) == path.visited);
+        assert_eq!(path.render(false), construct_visited_input());
 
         assert_eq!(41, path.count_visited_positions());
 
 
     }
+
+    #[test]
+    fn test_solution() {
+        let map = Day6::parse(construct_input()).unwrap();
+
+        assert_eq!(Day6::part1(&map), 41);
+        assert_eq!(Day6::part2(&map), None);
+    }
     #[test]
     fn test_setup_free_space() {
         let map = create_map();
 
         let free_space = Path::setup_free_space(&map);
-        println!("{:?}", free_space);
 
-        assert!(false);
+        // Free squares up, right, down and left, counting the square itself; zeros on a block
+        assert_eq!(free_space.get(6,4), Some(&(6,6,4,3)));
+        assert_eq!(free_space.get(0,0), Some(&(1,4,8,1)));
+        assert_eq!(free_space.get(0,4), Some(&(0,0,0,0)));
+    }
+
+    #[test]
+    fn test_leave_upwards_and_leftwards() {
+        let up = Day6::parse("...\n.^.\n...").unwrap();
+        assert_eq!(Day6::part1(&up), 2);
+
+        let left = Day6::parse("\
+.#..
+...#
+.^..
+..#.").unwrap();
+        assert_eq!(Day6::part1(&left), 5);
     }
 
 }
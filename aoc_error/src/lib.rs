@@ -0,0 +1,155 @@
+use std::fmt;
+use std::io;
+
+/// The failures shared by the readers, the grid types and the days.
+/// Lines and columns are counted from 1, rows and columns of a grid from 0.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse { line: usize, column: usize, message: String },
+    ShapeMismatch { expected: (usize, usize), found: (usize, usize) },
+    OutOfBounds { row: usize, col: usize, nrows: usize, ncols: usize },
+    IndexOutOfBounds { index: Vec<usize>, shape: Vec<usize> },
+    InvalidArgument(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The 1-based line and column of the character starting at byte `offset` in `text`.
+pub fn location(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// The whitespace separated words of `text`, each with its byte offset, so that parse
+/// errors can point at them with [`Error::parse_at`].
+pub fn split_words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.char_indices()
+        .filter(move |&(i, c)| !c.is_whitespace() && text[..i].chars().next_back().is_none_or(char::is_whitespace))
+        .map(move |(start, _)| {
+            let end = text[start..].find(char::is_whitespace).map_or(text.len(), |len| start + len);
+            (start, &text[start..end])
+        })
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Error {
+        Error::Parse { line, column, message: message.into() }
+    }
+
+    /// A parse error at byte `offset` in `text`.
+    pub fn parse_at(text: &str, offset: usize, message: impl Into<String>) -> Error {
+        let (line, column) = location(text, offset);
+
+        Error::parse(line, column, message)
+    }
+
+    pub fn invalid_argument(message: impl Into<String>) -> Error {
+        Error::InvalidArgument(message.into())
+    }
+
+    /// Moves the location of a parse error down by `lines`, for content that was cut out of
+    /// a larger text. Other errors are returned untouched.
+    pub fn offset_lines(self, lines: usize) -> Error {
+        match self {
+            Error::Parse { line, column, message } => Error::Parse { line: line + lines, column, message },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Parse { line, column, message } =>
+                write!(f, "Parse error at line {}, column {}: {}", line, column, message),
+            Error::ShapeMismatch { expected, found } =>
+                write!(f, "Shape mismatch: expected {}x{}, found {}x{}",
+                       expected.0, expected.1, found.0, found.1),
+            Error::OutOfBounds { row, col, nrows, ncols } =>
+                write!(f, "Index ({}, {}) out of bounds for a {}x{} grid", row, col, nrows, ncols),
+            Error::IndexOutOfBounds { index, shape } =>
+                write!(f, "Index {:?} out of bounds for a grid of shape {:?}", index, shape),
+            Error::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let text = "abc\ndéf\n\nx";
+
+        assert_eq!(location(text, 0), (1, 1));
+        assert_eq!(location(text, 2), (1, 3));
+        assert_eq!(location(text, 4), (2, 1));
+        assert_eq!(location(text, 7), (2, 3));
+        assert_eq!(location(text, 9), (3, 1));
+        assert_eq!(location(text, 10), (4, 1));
+        assert_eq!(location(text, 100), (4, 2));
+    }
+
+    #[test]
+    fn test_parse_at() {
+        let err = Error::parse_at("1 2\n3 x", 6, "invalid number");
+
+        assert!(matches!(err, Error::Parse { line: 2, column: 3, .. }));
+        assert_eq!(err.to_string(), "Parse error at line 2, column 3: invalid number");
+    }
+
+    #[test]
+    fn test_split_words() {
+        let words: Vec<(usize, &str)> = split_words("  12 ab\té3\n x").collect();
+        assert_eq!(words, vec![(2, "12"), (5, "ab"), (8, "é3"), (13, "x")]);
+
+        assert_eq!(split_words(" \n ").count(), 0);
+    }
+
+    #[test]
+    fn test_offset_lines() {
+        assert!(matches!(Error::parse(2, 3, "x").offset_lines(5), Error::Parse { line: 7, column: 3, .. }));
+        assert!(matches!(Error::invalid_argument("x").offset_lines(5), Error::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn test_display() {
+        let err = Error::OutOfBounds { row: 3, col: 4, nrows: 2, ncols: 2 };
+        assert_eq!(err.to_string(), "Index (3, 4) out of bounds for a 2x2 grid");
+
+        let err = Error::IndexOutOfBounds { index: vec![0, 3, 1], shape: vec![2, 2, 2] };
+        assert_eq!(err.to_string(), "Index [0, 3, 1] out of bounds for a grid of shape [2, 2, 2]");
+
+        let err = Error::ShapeMismatch { expected: (2, 3), found: (3, 2) };
+        assert_eq!(err.to_string(), "Shape mismatch: expected 2x3, found 3x2");
+    }
+
+    #[test]
+    fn test_from_io() {
+        let err: Error = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+
+        assert!(matches!(err, Error::Io(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
@@ -1,21 +1,24 @@
 use std::fs;
 
-pub fn parse_content(content: &str, number_of_columns: usize)
-        -> Result<Vec<Vec<usize>>, Box<dyn std::error::Error>> {
+use aoc_error::{split_words, Error, Result};
+
+pub fn parse_content(content: &str, number_of_columns: usize) -> Result<Vec<Vec<usize>>> {
 
     let mut columns: Vec<Vec<usize>> = vec![Vec::new(); number_of_columns];
 
-    for line in content.lines().filter(|line| !line.trim().is_empty()) {
-        let words: Vec<&str> = line.split_whitespace().collect();
+    for (n, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let words: Vec<(usize, &str)> = split_words(line).collect();
 
         if words.len() != number_of_columns {
-            return Err(format!("Invalid input line: {}", line).into());
+            return Err(Error::parse(n + 1, 1,
+                format!("expected {} columns, found {}", number_of_columns, words.len())));
         }
 
         words.iter()
             .enumerate()
-            .try_for_each(|(i, word)| -> Result<(), Box<dyn std::error::Error>> {
-                let item = word.parse::<usize>()?;
+            .try_for_each(|(i, &(offset, word))| -> Result<()> {
+                let item = word.parse::<usize>()
+                    .map_err(|err| Error::parse_at(line, offset, format!("{}: {}", err, word)).offset_lines(n))?;
                 columns[i].push(item);
                 Ok(())
             })?;
@@ -23,8 +26,7 @@ pub fn parse_content(content: &str, number_of_columns: usize)
     Ok(columns)
 }
 
-pub fn read_columns_from_file(filename: &str, number_of_columns: usize)
-        -> Result<Vec<Vec<usize>>, Box<dyn std::error::Error>> {
+pub fn read_columns_from_file(filename: &str, number_of_columns: usize) -> Result<Vec<Vec<usize>>> {
     let content = fs::read_to_string(filename)?;
     let result = parse_content(&content, number_of_columns)?;
 
@@ -36,7 +38,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_content() -> Result<(), Box<dyn std::error::Error>> {
+    fn test_parse_content() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let content =  "\
 7 6 4 2 1
 1 2 7 8 9
@@ -44,7 +46,7 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        let actual = vec![
+        let actual = [
             [7,1,9,1,8,1],
             [6,2,7,3,6,3],
             [4,7,6,2,4,6],
@@ -63,7 +65,7 @@ mod tests {
             Ok(())
         } else {
             let mut s: String = "".to_string();
-            let _ = columns.iter()
+            columns.iter()
                 .zip(actual.iter())
                 .for_each(|(a, b)| {
                     if a != b {
@@ -72,7 +74,16 @@ mod tests {
                 });
 
             s.pop();s.pop();
-            return Err(s.into());
+            Err(s.into())
         }
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_content("1 2\n3", 2).unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 2, column: 1, .. }));
+
+        let err = parse_content("1 2\n3   y", 2).unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 2, column: 5, .. }));
+    }
 }
@@ -1,23 +1,378 @@
+use std::fs;
+use std::io::{self, Read};
+
+use aoc_error::{Error, Result};
+
+pub static USAGE: &str = "\
+Usage: aoc <COMMAND> [OPTIONS] [INPUT...]
+       aoc new <DAY> [--root <DIR>]
+
+Commands:
+  run                    Solve one, several or all registered days
+  bench                  Time parsing and solving of the selected days
+  new                    Create the aoc_2024_dayN crate for a new day and
+                         register it with the runner
+
+Options:
+  -d, --day <N[,N...]>   Day(s) to run, may be repeated [default: all days]
+  -i, --input <PATH>     Input file, or `-` to read from stdin; may be repeated and
+                         needs a single day. Trailing arguments are inputs as well
+      --input-dir <DIR>  Directory holding dayN.txt inputs [default: inputs]
+  -p, --part <1|2>       Only solve the given part [default: both]
+  -a, --answers <FILE>   Check the answers against a TOML file of known answers
+                         and fail if any of them differ
+  -f, --format <FORMAT>  Output format: text or json [default: text]. json prints
+                         one object per day and part, including timings
+  -v, --verbose          Print timings and log messages to stderr; repeat for more
+                         detail (-v info, -vv debug, -vvv trace)
+  -h, --help             Print this help
+
+New options:
+      --root <DIR>       Directory holding the aoc runner and the day crates
+                         [default: the current directory or its parent]
+
+Bench options:
+      --runs <N>         Timed runs per measurement [default: 10]
+      --warmup <N>       Untimed runs before measuring [default: 3]
+      --baseline <FILE>  Compare the medians with an earlier --save-baseline
+      --save-baseline <FILE>
+                         Store the measured timings as a baseline
+      --threshold <PERCENT>
+                         Slowdown that counts as a regression [default: 10]";
+
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub enum Command {
+    Run,
+    Bench,
+    New,
+}
+
+#[derive(Debug,PartialEq,Clone)]
+pub enum Input {
+    Stdin,
+    File(String),
+}
+
+impl Input {
+    pub fn parse(value: &str) -> Self {
+        if value == "-" {
+            Input::Stdin
+        } else {
+            Input::File(value.to_string())
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Input::Stdin => "-",
+            Input::File(path) => path,
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Input::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                Ok(content)
+            },
+            Input::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn includes_one(&self) -> bool {
+        *self != Part::Two
+    }
+
+    pub fn includes_two(&self) -> bool {
+        *self != Part::One
+    }
+}
+
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug,PartialEq)]
 pub struct Config {
-    pub file_path: String,
+    pub command: Command,
+    pub days: Vec<usize>,
+    pub inputs: Vec<Input>,
+    pub input_dir: String,
+    pub part: Part,
+    pub answers: Option<String>,
+    pub format: Format,
+    pub verbosity: usize,
+    pub help: bool,
+    pub runs: usize,
+    pub warmup: usize,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
+    pub threshold: f64,
+    pub root: Option<String>,
 }
 
-impl Config {
-    pub fn new(args: &[String]) -> Config {
-        if args.len() < 2 {
-            panic!("Need filename as first argument.");
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            command: Command::Run,
+            days: Vec::new(),
+            inputs: Vec::new(),
+            input_dir: "inputs".to_string(),
+            part: Part::Both,
+            answers: None,
+            format: Format::Text,
+            verbosity: 0,
+            help: false,
+            runs: 10,
+            warmup: 3,
+            baseline: None,
+            save_baseline: None,
+            threshold: 10.0,
+            root: None,
+        }
+    }
+}
+
+fn parse_days(value: &str, days: &mut Vec<usize>) -> Result<()> {
+    for word in value.split(',').filter(|word| !word.trim().is_empty()) {
+        let day = word.trim().parse::<usize>()
+            .map_err(|_| Error::invalid_argument(format!("Invalid day: {}", word)))?;
+
+        if !days.contains(&day) {
+            days.push(day);
         }
+    }
+    Ok(())
+}
 
-        let file_path = args[1].clone();
+fn parse_part(value: &str) -> Result<Part> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(Error::invalid_argument(format!("Invalid part: {}, expected 1 or 2", value))),
+    }
+}
 
-        Config { file_path }
+fn parse_format(value: &str) -> Result<Format> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(Error::invalid_argument(format!("Invalid format: {}, expected text or json", value))),
     }
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T> {
+    value.parse::<T>()
+        .map_err(|_| Error::invalid_argument(format!("Invalid value for {}: {}", option, value)))
+}
+
+impl Config {
+    /// Parses the command line, where `args[0]` is the program name.
+    /// A requested help is reported through `help` rather than as an error.
+    pub fn build(args: &[String]) -> Result<Config> {
+        let mut config = Config::default();
+        let mut args = args.iter().skip(1).peekable();
+
+        match args.peek().map(|arg| arg.as_str()) {
+            Some("run") => { args.next(); },
+            Some("bench") => { args.next(); config.command = Command::Bench; },
+            Some("new") => { args.next(); config.command = Command::New; },
+            Some("-h") | Some("--help") => (),
+            Some(other) => return Err(Error::invalid_argument(format!("Unknown command: {}", other))),
+            None => return Err(Error::invalid_argument("Need a command as first argument.")),
+        }
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next()
+                .cloned()
+                .ok_or_else(|| Error::invalid_argument(format!("Missing value for {}", arg)));
 
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 2 {
-            return Err("Need filename as first argument.");
+            match arg.as_str() {
+                "-d" | "--day" => parse_days(&value()?, &mut config.days)?,
+                "-i" | "--input" => config.inputs.push(Input::parse(&value()?)),
+                "--input-dir" => config.input_dir = value()?,
+                "-p" | "--part" => config.part = parse_part(&value()?)?,
+                "-a" | "--answers" => config.answers = Some(value()?),
+                "-f" | "--format" => config.format = parse_format(&value()?)?,
+                "-v" | "--verbose" => config.verbosity += 1,
+                "-h" | "--help" => config.help = true,
+                "--runs" => config.runs = parse_number(arg, &value()?)?,
+                "--warmup" => config.warmup = parse_number(arg, &value()?)?,
+                "--baseline" => config.baseline = Some(value()?),
+                "--save-baseline" => config.save_baseline = Some(value()?),
+                "--threshold" => config.threshold = parse_number(arg, &value()?)?,
+                "--root" => config.root = Some(value()?),
+                "-" => config.inputs.push(Input::Stdin),
+                _ if arg.len() > 2 && arg[1..].chars().all(|c| c == 'v') =>
+                    config.verbosity += arg.len() - 1,
+                _ if arg.starts_with('-') => return Err(Error::invalid_argument(format!("Unknown argument: {}", arg))),
+                _ if config.command == Command::New => parse_days(arg, &mut config.days)?,
+                _ => config.inputs.push(Input::parse(arg)),
+            }
         }
 
-        Ok(Config::new(args))
+        if config.command == Command::New && config.days.len() != 1 && !config.help {
+            return Err(Error::invalid_argument("new needs exactly one day"));
+        }
+
+        if config.runs == 0 {
+            return Err(Error::invalid_argument("--runs needs to be at least 1"));
+        }
+
+        if !config.inputs.is_empty() && config.days.len() != 1 {
+            return Err(Error::invalid_argument("Inputs can only be given together with a single --day"));
+        }
+
+        if config.inputs.iter().filter(|&input| *input == Input::Stdin).count() > 1 {
+            return Err(Error::invalid_argument("Stdin can only be read once"));
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_build_defaults() {
+        let config = Config::build(&args("aoc run")).unwrap();
+
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_build_all_options() {
+        let config = Config::build(&args("aoc run --day 4 --input example.txt -p 2 -f text -v -v")).unwrap();
+
+        assert_eq!(config, Config {
+            days: vec![4],
+            inputs: vec![Input::File("example.txt".to_string())],
+            part: Part::Two,
+            verbosity: 2,
+            ..Config::default()
+        });
+    }
+
+    #[test]
+    fn test_build_verbosity() {
+        assert_eq!(Config::build(&args("aoc run -v")).unwrap().verbosity, 1);
+        assert_eq!(Config::build(&args("aoc run -vvv")).unwrap().verbosity, 3);
+        assert_eq!(Config::build(&args("aoc run -vv --verbose")).unwrap().verbosity, 3);
+    }
+
+    #[test]
+    fn test_build_several_days() {
+        let config = Config::build(&args("aoc run -d 1,3 -d 5 -d 3 --input-dir data")).unwrap();
+
+        assert_eq!(config.days, vec![1, 3, 5]);
+        assert_eq!(config.input_dir, "data");
+    }
+
+    #[test]
+    fn test_build_stdin() {
+        let config = Config::build(&args("aoc run -d 2 -i -")).unwrap();
+
+        assert_eq!(config.inputs, vec![Input::Stdin]);
+    }
+
+    #[test]
+    fn test_build_json() {
+        let config = Config::build(&args("aoc run --format json")).unwrap();
+
+        assert_eq!(config.format, Format::Json);
+    }
+
+    #[test]
+    fn test_build_answers() {
+        let config = Config::build(&args("aoc run -a answers.toml")).unwrap();
+
+        assert_eq!(config.answers, Some("answers.toml".to_string()));
+    }
+
+    #[test]
+    fn test_build_several_inputs() {
+        let config = Config::build(&args("aoc run -d 2 -i example.txt real.txt -")).unwrap();
+
+        assert_eq!(config.inputs, vec![
+            Input::File("example.txt".to_string()),
+            Input::File("real.txt".to_string()),
+            Input::Stdin,
+        ]);
+    }
+
+    #[test]
+    fn test_build_bench() {
+        let config = Config::build(&args("aoc bench -d 4 --runs 50 --warmup 5 --baseline old.json --save-baseline new.json --threshold 2.5")).unwrap();
+
+        assert_eq!(config, Config {
+            command: Command::Bench,
+            days: vec![4],
+            runs: 50,
+            warmup: 5,
+            baseline: Some("old.json".to_string()),
+            save_baseline: Some("new.json".to_string()),
+            threshold: 2.5,
+            ..Config::default()
+        });
+    }
+
+    #[test]
+    fn test_build_new() {
+        let config = Config::build(&args("aoc new 7")).unwrap();
+        assert_eq!(config, Config { command: Command::New, days: vec![7], ..Config::default() });
+
+        assert_eq!(Config::build(&args("aoc new -d 8")).unwrap().days, vec![8]);
+        assert_eq!(Config::build(&args("aoc new 7 --root ..")).unwrap().root, Some("..".to_string()));
+        assert!(Config::build(&args("aoc new --help")).unwrap().help);
+    }
+
+    #[test]
+    fn test_build_help() {
+        assert!(Config::build(&args("aoc --help")).unwrap().help);
+        assert!(Config::build(&args("aoc run -d 1 -h")).unwrap().help);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert!(Config::build(&args("aoc")).is_err());
+        assert!(Config::build(&args("aoc walk")).is_err());
+        assert!(Config::build(&args("aoc run --day")).is_err());
+        assert!(Config::build(&args("aoc run --day x")).is_err());
+        assert!(Config::build(&args("aoc run --part 3")).is_err());
+        assert!(Config::build(&args("aoc run --format xml")).is_err());
+        assert!(Config::build(&args("aoc run --input a.txt")).is_err());
+        assert!(Config::build(&args("aoc run -d 1,2 a.txt")).is_err());
+        assert!(Config::build(&args("aoc run -d 1 - -")).is_err());
+        assert!(Config::build(&args("aoc run --colour")).is_err());
+        assert!(Config::build(&args("aoc bench --runs many")).is_err());
+        assert!(Config::build(&args("aoc bench --runs 0")).is_err());
+        assert!(Config::build(&args("aoc new")).is_err());
+        assert!(Config::build(&args("aoc new 7 8")).is_err());
+        assert!(Config::build(&args("aoc new seven")).is_err());
+    }
+
+    #[test]
+    fn test_part() {
+        assert!(Part::One.includes_one() && !Part::One.includes_two());
+        assert!(!Part::Two.includes_one() && Part::Two.includes_two());
+        assert!(Part::Both.includes_one() && Part::Both.includes_two());
     }
 }
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[repr(usize)]
+#[derive(Debug,PartialEq,Eq,PartialOrd,Ord,Clone,Copy)]
+pub enum Level {
+    Error = 0,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+    /// Maps the number of `-v` flags onto a level, where no flag at all only shows warnings.
+    pub fn from_verbosity(verbosity: usize) -> Level {
+        Level::ALL[(Level::Warn as usize + verbosity).min(Level::Trace as usize)]
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+static LEVEL: AtomicUsize = AtomicUsize::new(Level::Warn as usize);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::ALL[LEVEL.load(Ordering::Relaxed)]
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as usize <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Writes the message to stderr, so that logging never mixes with the answers on stdout.
+/// Prefer the macros, which skip formatting altogether when the level is disabled.
+pub fn log(level: Level, args: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("[{}] {}", level, args);
+    }
+}
+
+#[macro_export]
+macro_rules! log_at {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::enabled($level) {
+            $crate::log($level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log_at!($crate::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log_at!($crate::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log_at!($crate::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log_at!($crate::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log_at!($crate::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_verbosity() {
+        assert_eq!(Level::from_verbosity(0), Level::Warn);
+        assert_eq!(Level::from_verbosity(1), Level::Info);
+        assert_eq!(Level::from_verbosity(2), Level::Debug);
+        assert_eq!(Level::from_verbosity(3), Level::Trace);
+        assert_eq!(Level::from_verbosity(10), Level::Trace);
+    }
+
+    // The level is global, so everything touching it lives in a single test.
+    #[test]
+    fn test_set_level() {
+        assert_eq!(level(), Level::Warn);
+        assert!(enabled(Level::Error));
+        assert!(enabled(Level::Warn));
+        assert!(!enabled(Level::Info));
+
+        set_level(Level::Trace);
+        assert!(enabled(Level::Trace));
+
+        set_level(Level::Error);
+        assert!(!enabled(Level::Warn));
+
+        let mut formatted = false;
+        debug!("{}", { formatted = true; "skipped" });
+        assert!(!formatted);
+
+        set_level(Level::Debug);
+        debug!("{}", { formatted = true; "printed" });
+        assert!(formatted);
+
+        set_level(Level::Warn);
+    }
+}
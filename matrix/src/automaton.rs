@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+use crate::{Coord, Matrix};
+
+/// How a run until the grid repeats ended, with steps counted from the starting grid.
+#[derive(Debug,PartialEq,Eq,Clone,Copy)]
+pub enum Outcome {
+    /// After `steps` steps the rule stopped changing anything.
+    FixedPoint { steps: usize },
+    /// The grid after `start` steps came back every `length` steps.
+    Cycle { start: usize, length: usize },
+}
+
+/// Applies a rule to every cell at once, given the cell and the grid before the step.
+#[derive(Debug,Clone)]
+pub struct Automaton<T> {
+    current: Matrix<T>,
+    next: Matrix<T>,
+    steps: usize,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    pub fn new(matrix: Matrix<T>) -> Self {
+        Automaton { next: matrix.clone(), current: matrix, steps: 0 }
+    }
+
+    pub fn grid(&self) -> &Matrix<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Matrix<T> {
+        self.current
+    }
+
+    /// How many steps have been taken so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Takes one step, returning whether any cell changed.
+    pub fn step<F>(&mut self, mut rule: F) -> bool
+            where F: FnMut(Coord, &Matrix<T>) -> T {
+        let ncols = self.current.ncols;
+        for (i, cell) in self.next.elements.iter_mut().enumerate() {
+            *cell = rule(Coord::new(i / ncols, i % ncols), &self.current);
+        }
+
+        mem::swap(&mut self.current, &mut self.next);
+        self.steps += 1;
+        self.current != self.next
+    }
+
+    /// Takes up to `n` steps, stopping early at a fixed point, and returns how many changed the grid.
+    pub fn run<F>(&mut self, n: usize, mut rule: F) -> usize
+            where F: FnMut(Coord, &Matrix<T>) -> T {
+        (0..n).take_while(|_| self.step(&mut rule)).count()
+    }
+}
+
+impl<T: Clone + Eq + Hash> Automaton<T> {
+    /// Steps until the grid repeats an earlier one, which never ends if it doesn't.
+    pub fn run_until_stable<F>(&mut self, mut rule: F) -> Outcome
+            where F: FnMut(Coord, &Matrix<T>) -> T {
+        let first = self.steps;
+        let mut seen = HashMap::new();
+
+        loop {
+            if let Some(&start) = seen.get(&self.current) {
+                let start = start - first;
+                return match self.steps - first - start {
+                    1 => Outcome::FixedPoint { steps: start },
+                    length => Outcome::Cycle { start, length },
+                };
+            }
+
+            seen.insert(self.current.clone(), self.steps);
+            self.step(&mut rule);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(coord: Coord, grid: &Matrix<char>) -> char {
+        let alive = grid.neighbors8(coord).filter(|&(_, &c)| c == '#').count();
+
+        match (grid[coord], alive) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let mut automaton = Automaton::new(".#.\n.#.\n.#.".parse().unwrap());
+
+        assert!(automaton.step(life));
+        assert_eq!(automaton.grid().to_string(), "...\n###\n...");
+        assert_eq!(automaton.steps(), 1);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut block = Automaton::new("....\n.##.\n.##.\n....".parse().unwrap());
+        assert_eq!(block.run(10, life), 0);
+        assert_eq!(block.steps(), 1);
+
+        let mut blinker = Automaton::new(".#.\n.#.\n.#.".parse().unwrap());
+        assert_eq!(blinker.run(3, life), 3);
+        assert_eq!(blinker.into_grid().to_string(), "...\n###\n...");
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut blinker = Automaton::new(".....\n..#..\n..#..\n..#..\n.....".parse().unwrap());
+
+        assert_eq!(blinker.run_until_stable(life), Outcome::Cycle { start: 0, length: 2 });
+    }
+
+    #[test]
+    fn test_seating() {
+        let seats: Matrix<char> = "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL".parse().unwrap();
+        let mut automaton = Automaton::new(seats);
+
+        let outcome = automaton.run_until_stable(|coord, grid| {
+            let occupied = grid.neighbors8(coord).filter(|&(_, &c)| c == '#').count();
+            match grid[coord] {
+                'L' if occupied == 0 => '#',
+                '#' if occupied >= 4 => 'L',
+                c => c,
+            }
+        });
+
+        assert_eq!(outcome, Outcome::FixedPoint { steps: 5 });
+        assert_eq!(automaton.grid().count_where(|&c| c == '#'), 37);
+    }
+}
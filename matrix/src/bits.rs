@@ -0,0 +1,259 @@
+use std::ops::Index;
+
+use aoc_error::{Error, Result};
+
+use crate::{Coord, Direction, Matrix};
+
+const WORD: usize = u64::BITS as usize;
+
+/// Booleans packed 64 to a word, with every row starting on a fresh word.
+#[derive(Debug,PartialEq,Eq,Clone,Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    stride: usize,
+    pub nrows: usize,
+    pub ncols: usize,
+}
+
+impl BitGrid {
+    /// A grid with every cell unset.
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        let stride = ncols.div_ceil(WORD);
+
+        BitGrid { words: vec![0; nrows * stride], stride, nrows, ncols }
+    }
+
+    fn position(&self, row: usize, col: usize) -> (usize, u64) {
+        (row * self.stride + col / WORD, 1 << (col % WORD))
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.row < self.nrows && coord.col < self.ncols
+    }
+
+    /// The neighbouring cell in `direction`, or `None` when that is off the grid.
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        (coord + direction).filter(|&next| self.contains(next))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<bool> {
+        if self.contains(Coord::new(row, col)) {
+            let (word, bit) = self.position(row, col);
+            Some(self.words[word] & bit != 0)
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) -> Result<()> {
+        if !self.contains(Coord::new(row, col)) {
+            return Err(Error::OutOfBounds { row, col, nrows: self.nrows, ncols: self.ncols });
+        }
+
+        let (word, bit) = self.position(row, col);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+        Ok(())
+    }
+
+    /// Sets the cell at `coord`, returning whether it was unset before. Panics off the grid.
+    pub fn insert(&mut self, coord: Coord) -> bool {
+        assert!(self.contains(coord), "{:?} is outside a {}x{} grid", coord, self.nrows, self.ncols);
+
+        let (word, bit) = self.position(coord.row, coord.col);
+        let unset = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        unset
+    }
+
+    /// Unsets every cell.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn count_row(&self, row: usize) -> usize {
+        self.row_words(row).iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// The set cells, row by row.
+    pub fn iter_ones(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let row = i / self.stride;
+            let first_col = (i % self.stride) * WORD;
+
+            let mut remaining = word;
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    return None;
+                }
+                let bit = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                Some(Coord::new(row, first_col + bit))
+            })
+        })
+    }
+
+    /// The words of one row. Column `col` is bit `col % 64` of word `col / 64`.
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        &self.words[row * self.stride..(row + 1) * self.stride]
+    }
+
+    /// Changes the words of one row with `f`, then clears the bits past the last column.
+    pub fn update_row_words<F>(&mut self, row: usize, f: F)
+            where F: FnOnce(&mut [u64]) {
+        let words = &mut self.words[row * self.stride..(row + 1) * self.stride];
+        f(words);
+
+        if let (Some(last), used @ 1..) = (words.last_mut(), self.ncols % WORD) {
+            *last &= (1 << used) - 1;
+        }
+    }
+
+    fn combine<F>(&mut self, other: &BitGrid, f: F) -> Result<()>
+            where F: Fn(u64, u64) -> u64 {
+        if (self.nrows, self.ncols) != (other.nrows, other.ncols) {
+            return Err(Error::ShapeMismatch { expected: (self.nrows, self.ncols), found: (other.nrows, other.ncols) });
+        }
+
+        for (word, &theirs) in self.words.iter_mut().zip(&other.words) {
+            *word = f(*word, theirs);
+        }
+        Ok(())
+    }
+
+    /// Sets every cell that is set in `other`.
+    pub fn union_with(&mut self, other: &BitGrid) -> Result<()> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Unsets every cell that isn't set in `other`.
+    pub fn intersect_with(&mut self, other: &BitGrid) -> Result<()> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Unsets every cell that is set in `other`.
+    pub fn difference_with(&mut self, other: &BitGrid) -> Result<()> {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    pub fn to_matrix(&self) -> Matrix<bool> {
+        let mut matrix = Matrix::new(self.nrows, self.ncols, false);
+        for coord in self.iter_ones() {
+            matrix[coord] = true;
+        }
+        matrix
+    }
+}
+
+impl From<&Matrix<bool>> for BitGrid {
+    fn from(matrix: &Matrix<bool>) -> Self {
+        let mut grid = BitGrid::new(matrix.nrows, matrix.ncols);
+        for (coord, &value) in matrix.enumerate() {
+            if value {
+                grid.insert(coord);
+            }
+        }
+        grid
+    }
+}
+
+impl Index<Coord> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, coord: Coord) -> &bool {
+        match self.get(coord.row, coord.col) {
+            Some(true) => &true,
+            Some(false) => &false,
+            None => panic!("{:?} is outside a {}x{} grid", coord, self.nrows, self.ncols),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> BitGrid {
+        BitGrid::from(&Matrix::parse_with(text, |c| c == '#').unwrap())
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut bits = BitGrid::new(2, 100);
+
+        bits.set(1, 70, true).unwrap();
+        assert_eq!(bits.get(1, 70), Some(true));
+        assert_eq!(bits.get(0, 70), Some(false));
+        assert_eq!(bits.get(2, 0), None);
+        assert!(bits.set(0, 100, true).is_err());
+
+        assert!(!bits.insert(Coord::new(1, 70)));
+        assert!(bits.insert(Coord::new(0, 63)));
+        assert!(bits[Coord::new(0, 63)]);
+        assert_eq!(bits.count_ones(), 2);
+        assert_eq!(bits.row_words(1), &[0, 1 << 6]);
+
+        bits.set(1, 70, false).unwrap();
+        assert_eq!(bits.count_row(1), 0);
+    }
+
+    #[test]
+    fn test_update_row_words() {
+        let mut bits = BitGrid::new(2, 70);
+
+        bits.update_row_words(1, |words| words.fill(u64::MAX));
+        assert_eq!(bits.count_row(1), 70);
+        assert_eq!(bits.count_ones(), 70);
+        assert_eq!(bits.row_words(1), &[u64::MAX, (1 << 6) - 1]);
+
+        let mut full = BitGrid::new(1, 64);
+        full.update_row_words(0, |words| words.fill(u64::MAX));
+        assert_eq!(full.count_ones(), 64);
+    }
+
+    #[test]
+    fn test_iter_ones() {
+        let mut bits = BitGrid::new(3, 130);
+        let coords = [Coord::new(0, 0), Coord::new(0, 64), Coord::new(1, 129), Coord::new(2, 5)];
+        for coord in coords {
+            bits.insert(coord);
+        }
+
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), coords);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = grid("##.\n.#.");
+        let b = grid(".##\n...");
+
+        let mut union = a.clone();
+        union.union_with(&b).unwrap();
+        assert_eq!(union, grid("###\n.#."));
+
+        let mut intersection = a.clone();
+        intersection.intersect_with(&b).unwrap();
+        assert_eq!(intersection, grid(".#.\n..."));
+
+        let mut difference = a.clone();
+        difference.difference_with(&b).unwrap();
+        assert_eq!(difference, grid("#..\n.#."));
+
+        assert!(matches!(difference.union_with(&BitGrid::new(3, 2)),
+                         Err(Error::ShapeMismatch { expected: (2, 3), found: (3, 2) })));
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix = Matrix::parse_with("#..#\n.##.", |c| c == '#').unwrap();
+
+        assert_eq!(BitGrid::from(&matrix).to_matrix(), matrix);
+    }
+}
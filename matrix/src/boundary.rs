@@ -0,0 +1,162 @@
+use crate::{Coord, Direction, Matrix, Position};
+
+/// What reading outside a matrix gives.
+#[derive(Debug,PartialEq,Eq,Clone,Copy)]
+pub enum Boundary<T> {
+    /// Nothing, like [`Matrix::get`].
+    Strict,
+    /// The cell on the far side, as if the matrix was the surface of a torus.
+    Wrap,
+    /// The nearest cell on the edge.
+    Clamp,
+    /// The given value, as if the matrix was surrounded by it.
+    Default(T),
+}
+
+/// A matrix read through a [`Boundary`] at signed positions that may lie outside it.
+#[derive(Debug,Clone)]
+pub struct Bounded<'a, T> {
+    matrix: &'a Matrix<T>,
+    boundary: Boundary<T>,
+}
+
+impl<T> Matrix<T> {
+    pub fn with_boundary(&self, boundary: Boundary<T>) -> Bounded<'_, T> {
+        Bounded { matrix: self, boundary }
+    }
+}
+
+impl<'a, T> Bounded<'a, T> {
+    /// The cell that `position` stands for, or `None` when there is none, as for defaults.
+    pub fn resolve(&self, position: Position) -> Option<Coord> {
+        let (nrows, ncols) = (self.matrix.nrows as isize, self.matrix.ncols as isize);
+        if nrows == 0 || ncols == 0 {
+            return None;
+        }
+
+        let (row, col) = match self.boundary {
+            Boundary::Wrap => (position.0.rem_euclid(nrows), position.1.rem_euclid(ncols)),
+            Boundary::Clamp => (position.0.clamp(0, nrows - 1), position.1.clamp(0, ncols - 1)),
+            Boundary::Strict | Boundary::Default(_) => position,
+        };
+
+        if (0..nrows).contains(&row) && (0..ncols).contains(&col) {
+            Some(Coord::new(row as usize, col as usize))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        match (self.resolve((row, col)), &self.boundary) {
+            (Some(coord), _) => Some(&self.matrix[coord]),
+            (None, Boundary::Default(value)) => Some(value),
+            (None, _) => None,
+        }
+    }
+
+    /// The cell `delta` (rows, columns) away from `coord`, or `None` when there is no such cell.
+    pub fn offset(&self, coord: Coord, delta: (isize, isize)) -> Option<Coord> {
+        self.resolve((coord.row as isize + delta.0, coord.col as isize + delta.1))
+    }
+
+    /// The neighbouring cell in `direction`. Wrapping off one edge comes back on the other.
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        self.offset(coord, direction.delta())
+    }
+
+    /// The values at each of the `offsets` from `coord`, with the positions they were read from.
+    pub fn neighbors<I>(&self, coord: Coord, offsets: I) -> impl Iterator<Item = (Position, &T)>
+            where I: IntoIterator<Item = (isize, isize)> {
+        offsets.into_iter().filter_map(move |(drow, dcol)| {
+            let position = (coord.row as isize + drow, coord.col as isize + dcol);
+
+            match self.resolve(position) {
+                Some(next) => Some(((next.row as isize, next.col as isize), &self.matrix[next])),
+                None => self.get(position.0, position.1).map(|value| (position, value)),
+            }
+        })
+    }
+
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(coord, Direction::CARDINAL.map(Direction::delta))
+    }
+
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(coord, Direction::ALL.map(Direction::delta))
+    }
+
+    /// The values from (row, col) onwards, one `step` apart. Only strict rays end at the edge.
+    pub fn ray(&self, row: isize, col: isize, step: (isize, isize)) -> impl Iterator<Item = &T> {
+        (0..).map_while(move |k: isize| self.get(row + step.0 * k, col + step.1 * k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters;
+
+    #[test]
+    fn test_get() {
+        let grid = letters(2, 3);
+
+        assert_eq!(grid.with_boundary(Boundary::Strict).get(-1, 0), None);
+        assert_eq!(grid.with_boundary(Boundary::Strict).get(1, 2), Some(&'f'));
+        assert_eq!(grid.with_boundary(Boundary::Wrap).get(-1, 4), Some(&'e'));
+        assert_eq!(grid.with_boundary(Boundary::Wrap).get(5, -7), Some(&'f'));
+        assert_eq!(grid.with_boundary(Boundary::Clamp).get(-3, 10), Some(&'c'));
+        assert_eq!(grid.with_boundary(Boundary::Default('.')).get(2, 0), Some(&'.'));
+        assert_eq!(grid.with_boundary(Boundary::Default('.')).get(1, 0), Some(&'d'));
+
+        let empty: Matrix<char> = Matrix::new(0, 0, ' ');
+        assert_eq!(empty.with_boundary(Boundary::Wrap).get(0, 0), None);
+    }
+
+    #[test]
+    fn test_wrapping_robot() {
+        let floor = Matrix::new(7, 11, ());
+        let floor = floor.with_boundary(Boundary::Wrap);
+
+        let mut robot = Coord::new(4, 2);
+        for _ in 0..5 {
+            robot = floor.offset(robot, (-3, 2)).unwrap();
+        }
+        assert_eq!(robot, Coord::new(3, 1));
+        assert_eq!(floor.step(Coord::new(0, 0), Direction::UpLeft), Some(Coord::new(6, 10)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = letters(2, 3);
+
+        let wrapped: String = grid.with_boundary(Boundary::Wrap).neighbors4(Coord::new(0, 0)).map(|(_, &c)| c).collect();
+        assert_eq!(wrapped, "dbdc");
+
+        let clamped: String = grid.with_boundary(Boundary::Clamp).neighbors4(Coord::new(0, 0)).map(|(_, &c)| c).collect();
+        assert_eq!(clamped, "abda");
+
+        let padded = grid.with_boundary(Boundary::Default('.'));
+        let neighbors: Vec<(Position, &char)> = padded.neighbors4(Coord::new(0, 0)).collect();
+        assert_eq!(neighbors, vec![((-1, 0), &'.'), ((0, 1), &'b'), ((1, 0), &'d'), ((0, -1), &'.')]);
+
+        assert_eq!(grid.with_boundary(Boundary::Strict).neighbors8(Coord::new(0, 0)).count(), 3);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = letters(2, 3);
+
+        let strict: String = grid.with_boundary(Boundary::Strict).ray(0, 0, (0, 1)).collect();
+        assert_eq!(strict, "abc");
+
+        let wrapped: String = grid.with_boundary(Boundary::Wrap).ray(0, 0, (1, 1)).take(6).collect();
+        assert_eq!(wrapped, "aecdbf");
+
+        let clamped: String = grid.with_boundary(Boundary::Clamp).ray(1, 1, (0, 1)).take(4).collect();
+        assert_eq!(clamped, "efff");
+
+        let padded: String = grid.with_boundary(Boundary::Default('.')).ray(-1, 0, (1, 0)).take(4).collect();
+        assert_eq!(padded, ".ad.");
+    }
+}
@@ -0,0 +1,204 @@
+use std::ops::{Add, Index, IndexMut};
+
+use crate::Matrix;
+
+/// A cell of a matrix, counted from the top left corner.
+#[derive(Debug,PartialEq,Eq,Hash,PartialOrd,Ord,Clone,Copy,Default)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Coord { row, col }
+    }
+
+    pub fn manhattan(&self, other: Coord) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((row, col): (usize, usize)) -> Self {
+        Coord { row, col }
+    }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(coord: Coord) -> Self {
+        (coord.row, coord.col)
+    }
+}
+
+/// The eight compass directions, where up means towards row 0.
+#[repr(usize)]
+#[derive(Debug,PartialEq,Eq,Hash,Clone,Copy)]
+pub enum Direction {
+    Up = 0,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// All eight directions, clockwise from `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up, Direction::UpRight, Direction::Right, Direction::DownRight,
+        Direction::Down, Direction::DownLeft, Direction::Left, Direction::UpLeft,
+    ];
+
+    /// The direction `eighths` eighths of a full turn clockwise from this one.
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// Turns half as far as [`Direction::turn_right`], onto or off a diagonal.
+    pub fn turn_right_45(self) -> Direction {
+        self.rotate(1)
+    }
+
+    pub fn turn_left_45(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The (row, column) change of a single step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+}
+
+/// One step in `direction`, or `None` when that would go below row or column 0.
+impl Add<Direction> for Coord {
+    type Output = Option<Coord>;
+
+    fn add(self, direction: Direction) -> Option<Coord> {
+        let (drow, dcol) = direction.delta();
+
+        Some(Coord {
+            row: self.row.checked_add_signed(drow)?,
+            col: self.col.checked_add_signed(dcol)?,
+        })
+    }
+}
+
+impl<T> Matrix<T> {
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.row < self.nrows && coord.col < self.ncols
+    }
+
+    /// The neighbouring cell in `direction`, or `None` when that is off the matrix.
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        self.offset(coord, direction.delta())
+    }
+}
+
+/// Panics when `coord` is outside the matrix, like indexing a slice would.
+impl<T> Index<Coord> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        assert!(self.contains(coord), "{:?} out of bounds for a {}x{} matrix", coord, self.nrows, self.ncols);
+        &self.elements[coord.row * self.ncols + coord.col]
+    }
+}
+
+impl<T> IndexMut<Coord> for Matrix<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        assert!(self.contains(coord), "{:?} out of bounds for a {}x{} matrix", coord, self.nrows, self.ncols);
+        &mut self.elements[coord.row * self.ncols + coord.col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::UpLeft.turn_right_45(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        assert!(Direction::ALL.iter().all(|d| d.opposite().opposite() == *d));
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+    }
+
+    #[test]
+    fn test_delta_matches_opposite() {
+        for direction in Direction::ALL {
+            let (drow, dcol) = direction.delta();
+            assert_eq!(direction.opposite().delta(), (-drow, -dcol));
+        }
+    }
+
+    #[test]
+    fn test_add_direction() {
+        let coord = Coord::new(0, 2);
+
+        assert_eq!(coord + Direction::Down, Some(Coord::new(1, 2)));
+        assert_eq!(coord + Direction::Left, Some(Coord::new(0, 1)));
+        assert_eq!(coord + Direction::Up, None);
+        assert_eq!(Coord::new(3, 0) + Direction::DownLeft, None);
+    }
+
+    #[test]
+    fn test_step_and_index() {
+        let mut matrix = Matrix::new(2, 3, 0);
+
+        assert_eq!(matrix.step(Coord::new(1, 1), Direction::Right), Some(Coord::new(1, 2)));
+        assert_eq!(matrix.step(Coord::new(1, 2), Direction::Right), None);
+        assert_eq!(matrix.step(Coord::new(1, 2), Direction::Down), None);
+
+        matrix[Coord::new(1, 2)] = 7;
+        assert_eq!(matrix[Coord::new(1, 2)], 7);
+        assert_eq!(matrix.get(1, 2), Some(&7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let matrix = Matrix::new(2, 3, 0);
+
+        let _ = matrix[Coord::new(0, 3)];
+    }
+
+    #[test]
+    fn test_manhattan() {
+        assert_eq!(Coord::new(1, 5).manhattan(Coord::new(4, 2)), 6);
+        assert_eq!(Coord::from((2, 3)), Coord::new(2, 3));
+    }
+}
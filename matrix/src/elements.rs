@@ -0,0 +1,96 @@
+use aoc_error::{Error, Result};
+
+use crate::{Coord, Matrix};
+
+impl<T> Matrix<T> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.elements.iter_mut()
+    }
+
+    /// Every cell with its coordinates, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let ncols = self.ncols;
+
+        self.elements.iter().enumerate().map(move |(i, value)| (Coord::new(i / ncols, i % ncols), value))
+    }
+
+    /// A matrix of the same shape holding `f` of each cell.
+    pub fn map<U, F>(&self, f: F) -> Matrix<U>
+            where F: FnMut(&T) -> U {
+        Matrix { elements: self.elements.iter().map(f).collect(), nrows: self.nrows, ncols: self.ncols }
+    }
+
+    /// Combines the cells at the same coordinates of two matrices of the same shape.
+    pub fn zip_with<U, V, F>(&self, other: &Matrix<U>, mut f: F) -> Result<Matrix<V>>
+            where F: FnMut(&T, &U) -> V {
+        if (self.nrows, self.ncols) != (other.nrows, other.ncols) {
+            return Err(Error::ShapeMismatch { expected: (self.nrows, self.ncols), found: (other.nrows, other.ncols) });
+        }
+
+        Ok(Matrix {
+            elements: self.elements.iter().zip(&other.elements).map(|(a, b)| f(a, b)).collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        })
+    }
+
+    pub fn count_where<P>(&self, mut predicate: P) -> usize
+            where P: FnMut(&T) -> bool {
+        self.elements.iter().filter(|&value| predicate(value)).count()
+    }
+
+    /// The first cell, reading row by row, that satisfies `predicate`.
+    pub fn position<P>(&self, predicate: P) -> Option<Coord>
+            where P: FnMut(&T) -> bool {
+        self.elements.iter().position(predicate).map(|i| Coord::new(i / self.ncols, i % self.ncols))
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn fill(&mut self, value: T) {
+        self.elements.fill(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters;
+
+    fn grid() -> Matrix<u32> {
+        letters(2, 3).map(|&c| c as u32 - 'a' as u32 + 1)
+    }
+
+    #[test]
+    fn test_map_and_iter_mut() {
+        let mut grid = grid();
+
+        assert_eq!(grid.map(|&n| n % 2 == 0).to_string(), "falsetruefalse\ntruefalsetrue");
+
+        grid.iter_mut().for_each(|n| *n *= 10);
+        assert_eq!(grid.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30, 40, 50, 60]);
+
+        grid.fill(7);
+        assert_eq!(grid, Matrix::new(2, 3, 7));
+    }
+
+    #[test]
+    fn test_zip_with() {
+        let grid = grid();
+        let sums = grid.zip_with(&grid.map(|&n| n * 10), |a, b| a + b).unwrap();
+
+        assert_eq!(sums.to_string(), "112233\n445566");
+        assert!(matches!(grid.zip_with(&Matrix::new(3, 2, 0), |a, b| a + b),
+                         Err(Error::ShapeMismatch { expected: (2, 3), found: (3, 2) })));
+    }
+
+    #[test]
+    fn test_enumerate_and_search() {
+        let grid = grid();
+
+        assert_eq!(grid.enumerate().nth(4), Some((Coord::new(1, 1), &5)));
+        assert_eq!(grid.count_where(|&n| n > 2), 4);
+        assert_eq!(grid.position(|&n| n > 3), Some(Coord::new(1, 0)));
+        assert_eq!(grid.position(|&n| n > 6), None);
+    }
+}
@@ -0,0 +1,311 @@
+use std::ops::{Index, IndexMut};
+
+use aoc_error::{Error, Result};
+
+use crate::{Coord, Direction, Matrix};
+
+/// A dense grid with any number of dimensions, laid out like a [`Matrix`] when `N` is 2.
+#[derive(Debug,PartialEq,Clone)]
+pub struct Grid<T, const N: usize> {
+    elements: Vec<T>,
+    shape: [usize; N],
+}
+
+impl<T: Clone, const N: usize> Grid<T, N> {
+    pub fn new(shape: [usize; N], initial_value: T) -> Self {
+        Grid { elements: vec![initial_value; shape.iter().product()], shape }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.elements.fill(value);
+    }
+}
+
+impl<T, const N: usize> Grid<T, N> {
+    /// A grid of the given shape holding `elements` in storage order.
+    pub fn from_vec(shape: [usize; N], elements: Vec<T>) -> Result<Self> {
+        let size: usize = shape.iter().product();
+        if elements.len() != size {
+            return Err(Error::invalid_argument(format!("a grid of shape {:?} needs {} elements, found {}",
+                                                       shape, size, elements.len())));
+        }
+
+        Ok(Grid { elements, shape })
+    }
+
+    pub fn shape(&self) -> [usize; N] {
+        self.shape
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn contains(&self, index: [usize; N]) -> bool {
+        index.iter().zip(&self.shape).all(|(i, size)| i < size)
+    }
+
+    fn flat(&self, index: [usize; N]) -> usize {
+        index.iter().zip(&self.shape).fold(0, |flat, (i, size)| flat * size + i)
+    }
+
+    fn unflat(&self, mut flat: usize) -> [usize; N] {
+        let mut index = [0; N];
+        for (i, size) in index.iter_mut().zip(&self.shape).rev() {
+            *i = flat % size;
+            flat /= size;
+        }
+        index
+    }
+
+    pub fn get(&self, index: [usize; N]) -> Option<&T> {
+        if self.contains(index) { Some(&self.elements[self.flat(index)]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, index: [usize; N]) -> Option<&mut T> {
+        if self.contains(index) {
+            let flat = self.flat(index);
+            Some(&mut self.elements[flat])
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, index: [usize; N], value: T) -> Result<()> {
+        let shape = self.shape;
+        let cell = self.get_mut(index)
+            .ok_or_else(|| Error::IndexOutOfBounds { index: index.to_vec(), shape: shape.to_vec() })?;
+
+        *cell = value;
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.elements.iter_mut()
+    }
+
+    /// Every cell with its index, in storage order.
+    pub fn enumerate(&self) -> impl Iterator<Item = ([usize; N], &T)> {
+        self.elements.iter().enumerate().map(move |(flat, value)| (self.unflat(flat), value))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U, N>
+            where F: FnMut(&T) -> U {
+        Grid { elements: self.elements.iter().map(f).collect(), shape: self.shape }
+    }
+
+    pub fn count_where<P>(&self, mut predicate: P) -> usize
+            where P: FnMut(&T) -> bool {
+        self.elements.iter().filter(|&value| predicate(value)).count()
+    }
+
+    /// The first cell in storage order that satisfies `predicate`.
+    pub fn position<P>(&self, predicate: P) -> Option<[usize; N]>
+            where P: FnMut(&T) -> bool {
+        self.elements.iter().position(predicate).map(|flat| self.unflat(flat))
+    }
+
+    /// The cell `delta` away from `index`, or `None` when that is off the grid.
+    pub fn offset(&self, index: [usize; N], delta: [isize; N]) -> Option<[usize; N]> {
+        let mut next = [0; N];
+        for ((n, i), d) in next.iter_mut().zip(index).zip(delta) {
+            *n = i.checked_add_signed(d)?;
+        }
+
+        if self.contains(next) { Some(next) } else { None }
+    }
+
+    /// The cells at each of the `offsets` from `index` that lie inside the grid, with their values.
+    pub fn neighbors<I>(&self, index: [usize; N], offsets: I) -> impl Iterator<Item = ([usize; N], &T)>
+            where I: IntoIterator<Item = [isize; N]> {
+        offsets.into_iter()
+            .filter_map(move |delta| self.offset(index, delta))
+            .map(|next| (next, &self[next]))
+    }
+
+    /// The cells from `index` onwards in the direction of `step`, up to the edge.
+    pub fn ray(&self, index: [usize; N], step: [isize; N]) -> impl Iterator<Item = &T> {
+        let standing = step.iter().all(|&d| d == 0);
+
+        std::iter::successors(self.contains(index).then_some(index),
+                              move |&i| if standing { None } else { self.offset(i, step) })
+            .map(move |i| &self[i])
+    }
+}
+
+/// One step forwards and one backwards along each axis, for [`Grid::neighbors`].
+pub fn orthogonal_offsets<const N: usize>() -> Vec<[isize; N]> {
+    (0..N)
+        .flat_map(|axis| [-1, 1].map(|d| {
+            let mut delta = [0; N];
+            delta[axis] = d;
+            delta
+        }))
+        .collect()
+}
+
+/// Every combination of -1, 0 and 1 along the axes except staying put, for [`Grid::neighbors`].
+pub fn all_offsets<const N: usize>() -> Vec<[isize; N]> {
+    (0..3usize.pow(N as u32))
+        .map(|mut k| {
+            let mut delta = [0; N];
+            for d in delta.iter_mut().rev() {
+                *d = (k % 3) as isize - 1;
+                k /= 3;
+            }
+            delta
+        })
+        .filter(|delta| delta.iter().any(|&d| d != 0))
+        .collect()
+}
+
+impl<T: Clone> Grid<T, 3> {
+    /// The plane at `layer` along the first axis.
+    pub fn layer(&self, layer: usize) -> Option<Matrix<T>> {
+        let [nlayers, nrows, ncols] = self.shape;
+        if layer >= nlayers {
+            return None;
+        }
+
+        let start = layer * nrows * ncols;
+        Some(Matrix { elements: self.elements[start..start + nrows * ncols].to_vec(), nrows, ncols })
+    }
+}
+
+impl<T> Grid<T, 2> {
+    /// The cells above, right of, below and left of `index`, skipping those off the grid.
+    pub fn neighbors4(&self, index: [usize; 2]) -> impl Iterator<Item = ([usize; 2], &T)> {
+        self.neighbors(index, Direction::CARDINAL.map(|direction| direction.delta().into()))
+    }
+
+    /// All eight surrounding cells, clockwise from the one above `index`.
+    pub fn neighbors8(&self, index: [usize; 2]) -> impl Iterator<Item = ([usize; 2], &T)> {
+        self.neighbors(index, Direction::ALL.map(|direction| direction.delta().into()))
+    }
+}
+
+impl<T> From<Matrix<T>> for Grid<T, 2> {
+    fn from(matrix: Matrix<T>) -> Self {
+        let shape = [matrix.nrows, matrix.ncols];
+
+        Grid { elements: matrix.elements, shape }
+    }
+}
+
+impl<T, const N: usize> Index<[usize; N]> for Grid<T, N> {
+    type Output = T;
+
+    fn index(&self, index: [usize; N]) -> &T {
+        self.get(index).unwrap_or_else(|| panic!("{:?} is outside a grid of shape {:?}", index, self.shape))
+    }
+}
+
+impl<T, const N: usize> IndexMut<[usize; N]> for Grid<T, N> {
+    fn index_mut(&mut self, index: [usize; N]) -> &mut T {
+        let shape = self.shape;
+        self.get_mut(index).unwrap_or_else(|| panic!("{:?} is outside a grid of shape {:?}", index, shape))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T, 2> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        &self[[coord.row, coord.col]]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T, 2> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        &mut self[[coord.row, coord.col]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters;
+
+    #[test]
+    fn test_indexing() {
+        let mut cube = Grid::new([2, 3, 4], 0);
+        assert_eq!(cube.len(), 24);
+
+        cube[[1, 2, 3]] = 7;
+        cube.set([0, 1, 0], 5).unwrap();
+        assert_eq!(cube.get([1, 2, 3]), Some(&7));
+        assert_eq!(cube.get([2, 0, 0]), None);
+        assert!(matches!(cube.set([0, 3, 0], 1), Err(Error::IndexOutOfBounds { .. })));
+
+        assert_eq!(cube.position(|&v| v == 7), Some([1, 2, 3]));
+        assert_eq!(cube.enumerate().nth(4), Some(([0, 1, 0], &5)));
+        assert_eq!(cube.count_where(|&v| v > 0), 2);
+        assert_eq!(cube.layer(0).unwrap().to_string(), "0000\n5000\n0000");
+    }
+
+    #[test]
+    fn test_from_vec() {
+        assert!(Grid::from_vec([2, 2, 2], (0..8).collect()).is_ok());
+        assert!(Grid::from_vec([2, 2, 2], (0..7).collect()).is_err());
+    }
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(orthogonal_offsets::<2>(), vec![[-1, 0], [1, 0], [0, -1], [0, 1]]);
+        assert_eq!(orthogonal_offsets::<3>().len(), 6);
+        assert_eq!(all_offsets::<2>().len(), 8);
+        assert_eq!(all_offsets::<3>().len(), 26);
+        assert_eq!(all_offsets::<4>().len(), 80);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let cube = Grid::from_vec([3, 3, 3], (0..27).collect()).unwrap();
+
+        assert_eq!(cube.neighbors([1, 1, 1], all_offsets()).count(), 26);
+        assert_eq!(cube.neighbors([0, 0, 0], all_offsets()).count(), 7);
+        assert_eq!(cube.neighbors([0, 1, 1], orthogonal_offsets()).map(|(_, &v)| v).collect::<Vec<_>>(),
+                   vec![13, 1, 7, 3, 5]);
+        assert_eq!(cube.neighbors([0, 0, 0], [[2, 2, 2], [0, 0, 3]]).map(|(_, &v)| v).collect::<Vec<_>>(),
+                   vec![26]);
+    }
+
+    #[test]
+    fn test_neighbors4_and_neighbors8() {
+        let grid = Grid::from(letters(3, 3));
+
+        assert_eq!(grid.neighbors4([1, 1]).map(|(_, &c)| c).collect::<String>(), "bfhd");
+        assert_eq!(grid.neighbors8([1, 1]).map(|(_, &c)| c).collect::<String>(), "bcfihgda");
+        assert_eq!(grid.neighbors8([0, 2]).map(|(_, &c)| c).collect::<String>(), "feb");
+    }
+
+    #[test]
+    fn test_ray() {
+        let cube = Grid::from_vec([3, 3, 3], (0..27).collect()).unwrap();
+
+        assert_eq!(cube.ray([0, 0, 0], [1, 1, 1]).copied().collect::<Vec<_>>(), vec![0, 13, 26]);
+        assert_eq!(cube.ray([2, 1, 0], [-1, 0, 1]).copied().collect::<Vec<_>>(), vec![21, 13, 5]);
+        assert_eq!(cube.ray([1, 1, 1], [0, 0, 0]).count(), 1);
+        assert_eq!(cube.ray([3, 0, 0], [1, 0, 0]).count(), 0);
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        let matrix = letters(2, 2);
+
+        let mut grid = Grid::from(matrix);
+        assert_eq!(grid.shape(), [2, 2]);
+        assert_eq!(grid[Coord::new(1, 0)], 'c');
+
+        grid[Coord::new(1, 0)] = 'x';
+        assert_eq!(grid[[1, 0]], 'x');
+    }
+}
@@ -1,4 +1,36 @@
-#[derive(Debug,PartialEq)]
+use std::str::FromStr;
+
+use aoc_error::{Error, Result};
+
+mod automaton;
+mod bits;
+mod boundary;
+mod coord;
+mod elements;
+mod grid;
+mod lines;
+mod neighbors;
+mod pattern;
+mod regions;
+mod render;
+mod search;
+mod sparse;
+mod transform;
+
+pub use automaton::{Automaton, Outcome};
+pub use bits::BitGrid;
+pub use boundary::{Boundary, Bounded};
+pub use coord::{Coord, Direction};
+pub use grid::{all_offsets, orthogonal_offsets, Grid};
+pub use lines::Line;
+pub use pattern::Pattern;
+pub use regions::{Connectivity, Labels, Region};
+pub use render::{Colour, Render};
+pub use search::Paths;
+pub use sparse::{Position, SparseGrid};
+pub use transform::View;
+
+#[derive(Debug,PartialEq,Eq,Hash,Clone)]
 pub struct Matrix<T> {
     elements: Vec<T>,
     pub nrows: usize,
@@ -13,7 +45,9 @@ impl<T: Clone> Matrix<T> {
             ncols,
         }
     }
+}
 
+impl<T> Matrix<T> {
     pub fn get(&self, row: usize, col: usize) -> Option<&T> {
         if row < self.nrows && col < self.ncols {
             self.elements.get(row * self.ncols + col)
@@ -22,28 +56,81 @@ impl<T: Clone> Matrix<T> {
         }
     }
 
-    pub fn set(&mut self, row: usize, col: usize, value: T) -> Result<(), &str> {
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Result<()> {
         if row < self.nrows && col < self.ncols {
-            if let Some(elem) = self.elements.get_mut(row * self.ncols + col) {
-                *elem = value;
-                Ok(())
-            } else {
-                Err("Index out of bounds")
-            }
+            self.elements[row * self.ncols + col] = value;
+            Ok(())
         } else {
-            Err("Index out of bounds")
+            Err(Error::OutOfBounds { row, col, nrows: self.nrows, ncols: self.ncols })
         }
     }
 
     pub fn iter(&self) -> impl Iterator<Item=&T> {
         self.elements.iter()
     }
+
+    /// Builds a matrix from puzzle text, one character per cell, mapping each with `f`.
+    pub fn try_parse_with<F>(text: &str, mut f: F) -> Result<Matrix<T>>
+            where F: FnMut(char) -> Option<T> {
+        let mut elements = Vec::new();
+        let mut nrows = 0;
+        let mut ncols = 0;
+
+        for (i, line) in text.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let start = elements.len();
+
+            for (j, c) in line.chars().enumerate() {
+                if i > 0 && j == ncols {
+                    return Err(Error::parse(i + 1, j + 1, format!("expected {} columns, found {}",
+                                                                  ncols, line.chars().count())));
+                }
+                let value = f(c).ok_or_else(|| Error::parse(i + 1, j + 1, format!("unexpected character {:?}", c)))?;
+                elements.push(value);
+            }
+
+            let length = elements.len() - start;
+            if i == 0 {
+                ncols = length;
+            } else if length < ncols {
+                return Err(Error::parse(i + 1, length + 1, format!("expected {} columns, found {}", ncols, length)));
+            }
+            nrows += 1;
+        }
+
+        Ok(Matrix { elements, nrows, ncols })
+    }
+
+    /// Like [`Matrix::try_parse_with`] for mappings that accept every character.
+    pub fn parse_with<F>(text: &str, mut f: F) -> Result<Matrix<T>>
+            where F: FnMut(char) -> T {
+        Matrix::try_parse_with(text, |c| Some(f(c)))
+    }
+}
+
+/// Parses cells of any type that converts from a single character.
+impl<T: TryFrom<char>> FromStr for Matrix<T> {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Matrix<T>> {
+        Matrix::try_parse_with(text, |c| T::try_from(c).ok())
+    }
+}
+
+/// A matrix of consecutive letters from `a`, row by row, shared by the tests of the modules.
+#[cfg(test)]
+fn letters(nrows: usize, ncols: usize) -> Matrix<char> {
+    Matrix { elements: ('a'..).take(nrows * ncols).collect(), nrows, ncols }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_letters() {
+        assert_eq!(letters(2, 3), "abc\ndef".parse().unwrap());
+    }
+
     #[test]
     fn test_set_and_get() {
         let mut matrix = Matrix::new(3, 4, ' ');
@@ -52,4 +139,62 @@ mod tests {
         let val = matrix.get(1,2).unwrap();
         assert_eq!(*val, 'c');
     }
+
+    #[test]
+    fn test_set_out_of_bounds() {
+        let mut matrix = Matrix::new(3, 4, ' ');
+
+        assert!(matches!(matrix.set(3, 0, 'c'), Err(Error::OutOfBounds { row: 3, col: 0, nrows: 3, ncols: 4 })));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let matrix: Matrix<char> = "abc\ndef\n".parse().unwrap();
+
+        assert_eq!((matrix.nrows, matrix.ncols), (2, 3));
+        assert_eq!(matrix.get(1, 0), Some(&'d'));
+        assert_eq!(matrix.iter().collect::<String>(), "abcdef");
+    }
+
+    #[test]
+    fn test_parse_crlf_and_trailing_newlines() {
+        let matrix: Matrix<char> = "ab\r\ncd\r\n\r\n".parse().unwrap();
+
+        assert_eq!((matrix.nrows, matrix.ncols), (2, 2));
+        assert_eq!(matrix.get(1, 1), Some(&'d'));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let matrix: Matrix<char> = "".parse().unwrap();
+
+        assert_eq!((matrix.nrows, matrix.ncols), (0, 0));
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let matrix = Matrix::parse_with("#.\n.#", |c| c == '#').unwrap();
+
+        assert_eq!(matrix.iter().filter(|&&blocked| blocked).count(), 2);
+        assert_eq!(matrix.get(1, 1), Some(&true));
+    }
+
+    #[test]
+    fn test_try_parse_with() {
+        let matrix = Matrix::try_parse_with("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(matrix.get(1, 0), Some(&3));
+
+        let result = Matrix::try_parse_with("12\n3x", |c| c.to_digit(10));
+        assert!(matches!(result, Err(Error::Parse { line: 2, column: 2, .. })));
+    }
+
+    #[test]
+    fn test_parse_ragged_lines() {
+        let result = "abc\nab\nabc".parse::<Matrix<char>>();
+        assert!(matches!(result, Err(Error::Parse { line: 2, column: 3, .. })));
+
+        let result = "abc\nabc\nabcd".parse::<Matrix<char>>();
+        assert!(matches!(result, Err(Error::Parse { line: 3, column: 4, .. })));
+        assert_eq!(result.unwrap_err().to_string(), "Parse error at line 3, column 4: expected 3 columns, found 4");
+    }
 }
@@ -0,0 +1,189 @@
+use std::iter::FusedIterator;
+
+use crate::Matrix;
+
+/// The cells along a straight line through a matrix, one `step` apart, from either end.
+#[derive(Debug,Clone)]
+pub struct Line<'a, T> {
+    matrix: &'a Matrix<T>,
+    row: isize,
+    col: isize,
+    step: (isize, isize),
+    len: usize,
+}
+
+impl<'a, T> Line<'a, T> {
+    fn new(matrix: &'a Matrix<T>, row: usize, col: usize, step: (isize, isize), len: usize) -> Self {
+        Line { matrix, row: row as isize, col: col as isize, step, len }
+    }
+
+    fn at(&self, k: usize) -> &'a T {
+        let row = self.row + self.step.0 * k as isize;
+        let col = self.col + self.step.1 * k as isize;
+
+        &self.matrix.elements[row as usize * self.matrix.ncols + col as usize]
+    }
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let item = self.at(0);
+        self.row += self.step.0;
+        self.col += self.step.1;
+        self.len -= 1;
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Line<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(self.at(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Line<'_, T> {}
+
+impl<T> FusedIterator for Line<'_, T> {}
+
+impl<T> Matrix<T> {
+    /// How many steps fit from (row, col) before leaving the matrix, counting the cell itself.
+    fn steps_inside(&self, row: usize, col: usize, step: (isize, isize)) -> usize {
+        fn fit(start: usize, step: isize, size: usize) -> usize {
+            match step {
+                0 => usize::MAX,
+                s if s > 0 => (size - start - 1) / s as usize + 1,
+                s => start / s.unsigned_abs() + 1,
+            }
+        }
+
+        if row >= self.nrows || col >= self.ncols {
+            return 0;
+        }
+        if step == (0, 0) {
+            return 1;
+        }
+
+        fit(row, step.0, self.nrows).min(fit(col, step.1, self.ncols))
+    }
+
+    /// The cells from (row, col) onwards in the direction of `step`, up to the edge.
+    pub fn ray(&self, row: usize, col: usize, step: (isize, isize)) -> Line<'_, T> {
+        Line::new(self, row, col, step, self.steps_inside(row, col, step))
+    }
+
+    /// Row `row` from left to right.
+    pub fn row(&self, row: usize) -> Line<'_, T> {
+        self.ray(row, 0, (0, 1))
+    }
+
+    /// Column `col` from top to bottom.
+    pub fn col(&self, col: usize) -> Line<'_, T> {
+        self.ray(0, col, (1, 0))
+    }
+
+    /// Diagonal `k` running down and to the right, numbered from the bottom left corner.
+    pub fn diagonal(&self, k: usize) -> Line<'_, T> {
+        if k < self.nrows {
+            self.ray(self.nrows - 1 - k, 0, (1, 1))
+        } else {
+            self.ray(0, k + 1 - self.nrows, (1, 1))
+        }
+    }
+
+    /// Anti-diagonal `k` running down and to the left, the cells where `row + col == k`.
+    pub fn anti_diagonal(&self, k: usize) -> Line<'_, T> {
+        if k < self.ncols {
+            self.ray(0, k, (1, -1))
+        } else {
+            self.ray(k + 1 - self.ncols, self.ncols.saturating_sub(1), (1, -1))
+        }
+    }
+
+    fn number_of_diagonals(&self) -> usize {
+        if self.nrows == 0 || self.ncols == 0 { 0 } else { self.nrows + self.ncols - 1 }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Line<'_, T>> {
+        (0..self.nrows).map(|row| self.row(row))
+    }
+
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = Line<'_, T>> {
+        (0..self.ncols).map(|col| self.col(col))
+    }
+
+    pub fn diagonals(&self) -> impl DoubleEndedIterator<Item = Line<'_, T>> {
+        (0..self.number_of_diagonals()).map(|k| self.diagonal(k))
+    }
+
+    pub fn anti_diagonals(&self) -> impl DoubleEndedIterator<Item = Line<'_, T>> {
+        (0..self.number_of_diagonals()).map(|k| self.anti_diagonal(k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters;
+
+    fn collect<'a>(line: impl Iterator<Item = &'a char>) -> String {
+        line.collect()
+    }
+
+    #[test]
+    fn test_rows_and_cols() {
+        let matrix = letters(3, 4);
+
+        assert_eq!(matrix.rows().map(collect).collect::<Vec<_>>(), vec!["abcd", "efgh", "ijkl"]);
+        assert_eq!(matrix.cols().map(collect).collect::<Vec<_>>(), vec!["aei", "bfj", "cgk", "dhl"]);
+        assert_eq!(collect(matrix.row(1).rev()), "hgfe");
+        assert_eq!(collect(matrix.col(3).rev()), "lhd");
+        assert_eq!(matrix.row(3).len(), 0);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let matrix = letters(3, 4);
+
+        assert_eq!(matrix.diagonals().map(collect).collect::<Vec<_>>(),
+                   vec!["i", "ej", "afk", "bgl", "ch", "d"]);
+        assert_eq!(matrix.anti_diagonals().map(collect).collect::<Vec<_>>(),
+                   vec!["a", "be", "cfi", "dgj", "hk", "l"]);
+        assert_eq!(collect(matrix.diagonal(3).rev()), "lgb");
+    }
+
+    #[test]
+    fn test_ray() {
+        let matrix = letters(3, 4);
+
+        assert_eq!(collect(matrix.ray(1, 1, (0, 1))), "fgh");
+        assert_eq!(collect(matrix.ray(1, 1, (-1, -1))), "fa");
+        assert_eq!(collect(matrix.ray(2, 0, (-1, 1))), "ifc");
+        assert_eq!(collect(matrix.ray(0, 0, (1, 2))), "ag");
+        assert_eq!(collect(matrix.ray(1, 1, (0, 0))), "f");
+        assert_eq!(matrix.ray(5, 0, (0, 1)).count(), 0);
+    }
+
+    #[test]
+    fn test_empty_matrix() {
+        let matrix: Matrix<char> = "".parse().unwrap();
+
+        assert_eq!(matrix.diagonals().count(), 0);
+        assert_eq!(matrix.anti_diagonals().count(), 0);
+    }
+}
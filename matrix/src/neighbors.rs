@@ -0,0 +1,71 @@
+use crate::{Coord, Direction, Matrix};
+
+impl<T> Matrix<T> {
+    /// The cell `delta` (rows, columns) away from `coord`, or `None` when that is off the matrix.
+    pub fn offset(&self, coord: Coord, delta: (isize, isize)) -> Option<Coord> {
+        let next = Coord {
+            row: coord.row.checked_add_signed(delta.0)?,
+            col: coord.col.checked_add_signed(delta.1)?,
+        };
+
+        if self.contains(next) { Some(next) } else { None }
+    }
+
+    /// The cells at each of the `offsets` from `coord` that lie inside the matrix, with their values.
+    pub fn neighbors<I>(&self, coord: Coord, offsets: I) -> impl Iterator<Item = (Coord, &T)>
+            where I: IntoIterator<Item = (isize, isize)> {
+        offsets.into_iter()
+            .filter_map(move |delta| self.offset(coord, delta))
+            .map(|next| (next, &self[next]))
+    }
+
+    /// The cells above, right of, below and left of `coord`, skipping those off the matrix.
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors(coord, Direction::CARDINAL.map(Direction::delta))
+    }
+
+    /// All eight surrounding cells, clockwise from the one above `coord`.
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors(coord, Direction::ALL.map(Direction::delta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters;
+
+    fn values<'a>(neighbors: impl Iterator<Item = (Coord, &'a char)>) -> String {
+        neighbors.map(|(_, &c)| c).collect()
+    }
+
+    #[test]
+    fn test_neighbors4() {
+        let matrix = letters(3, 3);
+
+        assert_eq!(values(matrix.neighbors4(Coord::new(1, 1))), "bfhd");
+        assert_eq!(values(matrix.neighbors4(Coord::new(0, 0))), "bd");
+
+        let coords: Vec<Coord> = matrix.neighbors4(Coord::new(2, 2)).map(|(coord, _)| coord).collect();
+        assert_eq!(coords, vec![Coord::new(1, 2), Coord::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        let matrix = letters(3, 3);
+
+        assert_eq!(values(matrix.neighbors8(Coord::new(1, 1))), "bcfihgda");
+        assert_eq!(values(matrix.neighbors8(Coord::new(0, 2))), "feb");
+        assert_eq!(matrix.neighbors8(Coord::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn test_custom_stencil() {
+        let matrix = letters(3, 3);
+        let knight = [(-2, -1), (-2, 1), (-1, 2), (1, 2), (2, 1), (2, -1), (1, -2), (-1, -2)];
+
+        assert_eq!(values(matrix.neighbors(Coord::new(0, 0), knight)), "fh");
+        assert_eq!(values(matrix.neighbors(Coord::new(1, 1), [(0, 0)])), "e");
+        assert_eq!(matrix.offset(Coord::new(0, 0), (0, 3)), None);
+    }
+}
@@ -0,0 +1,139 @@
+use aoc_error::Result;
+
+use crate::{Coord, Matrix, View};
+
+/// A small grid to look for in a matrix, where `None` cells match anything.
+#[derive(Debug,PartialEq,Clone)]
+pub struct Pattern<T> {
+    pub cells: Matrix<Option<T>>,
+}
+
+impl<T> Pattern<T> {
+    pub fn new(cells: Matrix<Option<T>>) -> Self {
+        Pattern { cells }
+    }
+
+    /// Whether `view` has the same size as the pattern and agrees with it on every fixed cell.
+    pub fn matches(&self, view: &View<'_, T>) -> bool where T: PartialEq {
+        view.nrows == self.cells.nrows
+            && view.ncols == self.cells.ncols
+            && view.iter().zip(self.cells.iter()).all(|(value, cell)| match cell {
+                Some(expected) => expected == value,
+                None => true,
+            })
+    }
+}
+
+impl Pattern<char> {
+    /// Reads a pattern from text like a matrix, where `wildcard` marks the cells that match anything.
+    pub fn parse(text: &str, wildcard: char) -> Result<Self> {
+        Ok(Pattern::new(Matrix::parse_with(text, |c| if c == wildcard { None } else { Some(c) })?))
+    }
+}
+
+impl<T: Clone + PartialEq> Pattern<T> {
+    /// The distinct patterns among the four rotations of this one and of its mirror image.
+    pub fn orientations(&self) -> Vec<Pattern<T>> {
+        let mirrored = self.cells.flip_h();
+        let candidates = [
+            self.cells.clone(), self.cells.rotate90(), self.cells.rotate180(), self.cells.rotate270(),
+            mirrored.rotate90(), mirrored.rotate180(), mirrored.rotate270(), mirrored,
+        ];
+
+        let mut orientations: Vec<Pattern<T>> = Vec::new();
+        for cells in candidates {
+            let pattern = Pattern::new(cells);
+            if !orientations.contains(&pattern) {
+                orientations.push(pattern);
+            }
+        }
+        orientations
+    }
+}
+
+impl<T: PartialEq> Matrix<T> {
+    /// The top left corners of all places where `pattern` matches, row by row.
+    pub fn find(&self, pattern: &Pattern<T>) -> Vec<Coord> {
+        self.windows(pattern.cells.nrows, pattern.cells.ncols)
+            .filter(|view| pattern.matches(view))
+            .map(|view| view.origin)
+            .collect()
+    }
+}
+
+impl<T: Clone + PartialEq> Matrix<T> {
+    /// The regions where some rotation or reflection of `pattern` matches, each region once,
+    /// sorted by top left corner and then by shape.
+    pub fn find_all_orientations(&self, pattern: &Pattern<T>) -> Vec<View<'_, T>> {
+        let mut found: Vec<View<'_, T>> = pattern.orientations()
+            .iter()
+            .flat_map(|orientation| self.windows(orientation.cells.nrows, orientation.cells.ncols)
+                .filter(|view| orientation.matches(view))
+                .collect::<Vec<_>>())
+            .collect();
+
+        found.sort_by_key(|view| (view.origin, view.nrows, view.ncols));
+        found.dedup_by_key(|view| (view.origin, view.nrows, view.ncols));
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regions(views: Vec<View<'_, char>>) -> Vec<(Coord, usize, usize)> {
+        views.iter().map(|view| (view.origin, view.nrows, view.ncols)).collect()
+    }
+
+    #[test]
+    fn test_find() {
+        let matrix: Matrix<char> = "abab\nbaba\nabab".parse().unwrap();
+        let pattern = Pattern::parse("a.\n.a", '.').unwrap();
+
+        assert_eq!(matrix.find(&pattern), vec![Coord::new(0, 0), Coord::new(0, 2), Coord::new(1, 1)]);
+        assert!(matrix.find(&Pattern::parse("c", '.').unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_larger_than_matrix() {
+        let matrix: Matrix<char> = "ab".parse().unwrap();
+
+        assert!(matrix.find(&Pattern::parse("ab\nab", '.').unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_orientations() {
+        assert_eq!(Pattern::parse("ab\ncd", '.').unwrap().orientations().len(), 8);
+        assert_eq!(Pattern::parse("ab\nba", '.').unwrap().orientations().len(), 2);
+        assert_eq!(Pattern::parse("M.M\n.A.\nS.S", '.').unwrap().orientations().len(), 4);
+        assert_eq!(Pattern::parse("x", '.').unwrap().orientations().len(), 1);
+    }
+
+    #[test]
+    fn test_find_all_orientations() {
+        let matrix: Matrix<char> = "\
+..L.
+.LL.
+....
+L...
+LL..".parse().unwrap();
+        let pattern = Pattern::parse("L?\nLL", '?').unwrap();
+
+        assert_eq!(matrix.find(&pattern), vec![Coord::new(3, 0)]);
+        assert_eq!(regions(matrix.find_all_orientations(&pattern)), vec![(Coord::new(0, 1), 2, 2), (Coord::new(3, 0), 2, 2)]);
+
+        // Both "ab" on top and "ba" at the bottom match the same region.
+        let matrix: Matrix<char> = "ab\nba".parse().unwrap();
+        assert_eq!(regions(matrix.find_all_orientations(&Pattern::parse("ab\n..", '.').unwrap())),
+                   vec![(Coord::new(0, 0), 2, 2)]);
+    }
+
+    #[test]
+    fn test_find_all_orientations_not_square() {
+        let matrix: Matrix<char> = "XMAS\nM...\nA...\nS...".parse().unwrap();
+        let pattern = Pattern::parse("XMAS", '.').unwrap();
+
+        assert_eq!(regions(matrix.find_all_orientations(&pattern)), vec![(Coord::new(0, 0), 1, 4), (Coord::new(0, 0), 4, 1)]);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{Coord, Direction, Matrix};
+
+/// Which cells count as touching: only those sharing an edge, or corners as well.
+#[derive(Debug,PartialEq,Eq,Clone,Copy)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::CARDINAL,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// A connected group of equal cells, measured along the edges of the cells.
+#[derive(Debug,PartialEq,Eq,Clone)]
+pub struct Region {
+    pub label: usize,
+    pub area: usize,
+    /// The number of cell edges between the region and anything else.
+    pub perimeter: usize,
+    /// The number of straight stretches of fence around the region, inside holes included.
+    pub sides: usize,
+    pub top_left: Coord,
+    pub bottom_right: Coord,
+}
+
+/// Every cell labelled with the index of its region in `regions`.
+#[derive(Debug,PartialEq,Clone)]
+pub struct Labels {
+    pub labels: Matrix<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T: PartialEq> Matrix<T> {
+    /// Calls `visit` for every cell connected to `seed` through cells equal to it, the seed included.
+    fn fill_region<F>(&self, seed: Coord, connectivity: Connectivity, seen: &mut Matrix<bool>, mut visit: F)
+            where F: FnMut(Coord) {
+        let mut pending = vec![seed];
+        seen[seed] = true;
+
+        while let Some(coord) = pending.pop() {
+            visit(coord);
+
+            for &direction in connectivity.directions() {
+                if let Some(next) = self.step(coord, direction) {
+                    if !seen[next] && self[next] == self[seed] {
+                        seen[next] = true;
+                        pending.push(next);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The cells of the region around `seed`, sorted.
+    pub fn flood_fill(&self, seed: Coord, connectivity: Connectivity) -> Vec<Coord> {
+        let mut seen = Matrix::new(self.nrows, self.ncols, false);
+        let mut region = Vec::new();
+
+        self.fill_region(seed, connectivity, &mut seen, |coord| region.push(coord));
+        region.sort();
+        region
+    }
+
+    /// Splits the matrix into regions of equal, connected cells, numbered in reading order.
+    pub fn label(&self, connectivity: Connectivity) -> Labels {
+        let mut seen = Matrix::new(self.nrows, self.ncols, false);
+        let mut labels = Matrix::new(self.nrows, self.ncols, 0);
+        let mut count = 0;
+
+        for (seed, _) in self.enumerate() {
+            if seen[seed] {
+                continue;
+            }
+
+            self.fill_region(seed, connectivity, &mut seen, |coord| labels[coord] = count);
+            count += 1;
+        }
+
+        let regions = region_statistics(&labels, count);
+        Labels { labels, regions }
+    }
+}
+
+fn region_statistics(labels: &Matrix<usize>, count: usize) -> Vec<Region> {
+    let mut regions: Vec<Region> = (0..count)
+        .map(|label| Region {
+            label,
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+            top_left: Coord::new(usize::MAX, usize::MAX),
+            bottom_right: Coord::new(0, 0),
+        })
+        .collect();
+
+    for (coord, &label) in labels.enumerate() {
+        let Coord { row, col } = coord;
+        let inside = |direction| labels.step(coord, direction).is_some_and(|next| labels[next] == label);
+        let region = &mut regions[label];
+
+        region.area += 1;
+        region.top_left = Coord::new(region.top_left.row.min(row), region.top_left.col.min(col));
+        region.bottom_right = Coord::new(region.bottom_right.row.max(row), region.bottom_right.col.max(col));
+
+        for direction in Direction::CARDINAL {
+            if !inside(direction) {
+                region.perimeter += 1;
+            }
+
+            // Every side ends in a corner, so counting corners counts sides
+            let next = direction.turn_right();
+            match (inside(direction), inside(next), inside(direction.turn_right_45())) {
+                (false, false, _) => region.sides += 1,
+                (true, true, false) => region.sides += 1,
+                _ => (),
+            }
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn garden() -> Matrix<char> {
+        "\
+AAAA
+BBCD
+BBCC
+EEEC".parse().unwrap()
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let garden = garden();
+
+        assert_eq!(garden.flood_fill(Coord::new(1, 2), Connectivity::Four),
+                   vec![Coord::new(1, 2), Coord::new(2, 2), Coord::new(2, 3), Coord::new(3, 3)]);
+        assert_eq!(garden.flood_fill(Coord::new(1, 3), Connectivity::Four), vec![Coord::new(1, 3)]);
+    }
+
+    #[test]
+    fn test_label() {
+        let Labels { labels, regions } = garden().label(Connectivity::Four);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(labels.iter().copied().collect::<Vec<_>>(),
+                   vec![0, 0, 0, 0, 1, 1, 2, 3, 1, 1, 2, 2, 4, 4, 4, 2]);
+
+        let price: usize = regions.iter().map(|region| region.area * region.perimeter).sum();
+        assert_eq!(price, 140);
+        let discounted: usize = regions.iter().map(|region| region.area * region.sides).sum();
+        assert_eq!(discounted, 80);
+
+        assert_eq!(regions[2], Region {
+            label: 2,
+            area: 4,
+            perimeter: 10,
+            sides: 8,
+            top_left: Coord::new(1, 2),
+            bottom_right: Coord::new(3, 3),
+        });
+    }
+
+    #[test]
+    fn test_sides_with_holes() {
+        let matrix: Matrix<char> = "\
+EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE".parse().unwrap();
+        let regions = matrix.label(Connectivity::Four).regions;
+
+        assert_eq!(regions.iter().map(|region| region.area * region.sides).sum::<usize>(), 236);
+    }
+
+    #[test]
+    fn test_eight_connectivity() {
+        let matrix: Matrix<char> = "\
+x.x
+.x.
+x..".parse().unwrap();
+
+        assert_eq!(matrix.label(Connectivity::Four).regions.len(), 7);
+
+        let regions = matrix.label(Connectivity::Eight).regions;
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].area, 4);
+        assert_eq!(regions[0].perimeter, 16);
+        assert_eq!((regions[0].top_left, regions[0].bottom_right), (Coord::new(0, 0), Coord::new(2, 2)));
+    }
+}
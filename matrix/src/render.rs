@@ -0,0 +1,152 @@
+use std::fmt;
+
+use crate::{Coord, Matrix};
+
+/// One row per line, so a `Matrix<char>` prints back as the text it was parsed from.
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.nrows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for value in self.row(row) {
+                write!(f, "{}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug,PartialEq,Eq,Clone,Copy)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+/// A picture of a matrix with one character per cell, plus overlays and ANSI highlights.
+pub struct Render<'a, T> {
+    matrix: &'a Matrix<T>,
+    cell: Box<dyn Fn(&T) -> char + 'a>,
+    overlays: Matrix<Option<char>>,
+    colours: Matrix<Option<Colour>>,
+    ansi: bool,
+}
+
+impl<'a, T> Render<'a, T> {
+    pub fn overlay<I>(mut self, coords: I, c: char) -> Self
+            where I: IntoIterator<Item = Coord> {
+        for coord in coords.into_iter().filter(|&coord| self.matrix.contains(coord)) {
+            self.overlays[coord] = Some(c);
+        }
+        self
+    }
+
+    pub fn highlight<I>(mut self, coords: I, colour: Colour) -> Self
+            where I: IntoIterator<Item = Coord> {
+        for coord in coords.into_iter().filter(|&coord| self.matrix.contains(coord)) {
+            self.colours[coord] = Some(colour);
+        }
+        self
+    }
+
+    /// Whether to write the ANSI escape codes for highlights, which only make sense on a terminal.
+    pub fn ansi(mut self, enabled: bool) -> Self {
+        self.ansi = enabled;
+        self
+    }
+}
+
+impl<T> fmt::Display for Render<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.matrix.nrows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for col in 0..self.matrix.ncols {
+                let coord = Coord::new(row, col);
+                let c = self.overlays[coord].unwrap_or_else(|| (self.cell)(&self.matrix[coord]));
+
+                match self.colours[coord] {
+                    Some(colour) if self.ansi => write!(f, "\x1b[{}m{}\x1b[0m", colour.ansi_code(), c)?,
+                    _ => write!(f, "{}", c)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Starts a picture of the matrix, drawing each cell as the character `cell` maps it to.
+    pub fn render<'a, F>(&'a self, cell: F) -> Render<'a, T>
+            where F: Fn(&T) -> char + 'a {
+        Render {
+            matrix: self,
+            cell: Box::new(cell),
+            overlays: Matrix::new(self.nrows, self.ncols, None),
+            colours: Matrix::new(self.nrows, self.ncols, None),
+            ansi: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let text = "#..\n.#.";
+        let matrix: Matrix<char> = text.parse().unwrap();
+
+        assert_eq!(matrix.to_string(), text);
+        assert_eq!(Matrix::new(2, 2, 7).to_string(), "77\n77");
+    }
+
+    #[test]
+    fn test_render() {
+        let matrix = Matrix::parse_with("#..\n.#.", |c| c == '#').unwrap();
+        let render = matrix.render(|&blocked| if blocked { '#' } else { '.' });
+
+        assert_eq!(render.to_string(), "#..\n.#.");
+    }
+
+    #[test]
+    fn test_overlays() {
+        let matrix = Matrix::new(2, 3, 0);
+        let path = [Coord::new(0, 0), Coord::new(0, 1), Coord::new(1, 1), Coord::new(5, 5)];
+
+        let render = matrix.render(|_| '.')
+            .overlay(path, 'o')
+            .overlay([Coord::new(1, 1)], '@');
+        assert_eq!(render.to_string(), "oo.\n.@.");
+    }
+
+    #[test]
+    fn test_highlight() {
+        let matrix: Matrix<char> = "ab".parse().unwrap();
+
+        let plain = matrix.render(|&c| c).highlight([Coord::new(0, 1)], Colour::Red);
+        assert_eq!(plain.to_string(), "ab");
+
+        let coloured = plain.ansi(true);
+        assert_eq!(coloured.to_string(), "a\x1b[31mb\x1b[0m");
+    }
+}
@@ -0,0 +1,285 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::{Coord, Direction, Matrix};
+
+/// Shortest distances from one start cell, with the predecessors to rebuild the paths.
+#[derive(Debug,Clone)]
+pub struct Paths {
+    pub start: Coord,
+    pub distances: Matrix<Option<usize>>,
+    predecessors: Matrix<Vec<Coord>>,
+}
+
+impl Paths {
+    fn new<T>(matrix: &Matrix<T>, start: Coord) -> Self {
+        let mut distances = Matrix::new(matrix.nrows, matrix.ncols, None);
+        distances[start] = Some(0);
+
+        Paths { start, distances, predecessors: Matrix::new(matrix.nrows, matrix.ncols, Vec::new()) }
+    }
+
+    /// Records reaching `to` from `from` at `distance`, returning whether `to` needs exploring.
+    fn relax(&mut self, from: Coord, to: Coord, distance: usize) -> bool {
+        if to == self.start {
+            return false;
+        }
+
+        match self.distances[to] {
+            Some(known) if known < distance => false,
+            Some(known) if known == distance => {
+                self.predecessors[to].push(from);
+                false
+            },
+            _ => {
+                self.distances[to] = Some(distance);
+                self.predecessors[to] = vec![from];
+                true
+            },
+        }
+    }
+
+    pub fn distance(&self, to: Coord) -> Option<usize> {
+        self.distances.get(to.row, to.col).copied().flatten()
+    }
+
+    /// One shortest path from the start to `to`, both included.
+    pub fn path(&self, to: Coord) -> Option<Vec<Coord>> {
+        self.distance(to)?;
+
+        let mut path = vec![to];
+        while let Some(&previous) = self.predecessors[*path.last().unwrap()].first() {
+            path.push(previous);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every shortest path from the start to `to` that repeats no cell. There can be very many.
+    pub fn all_paths(&self, to: Coord) -> Vec<Vec<Coord>> {
+        if self.distance(to).is_none() {
+            return Vec::new();
+        }
+
+        let mut paths = Vec::new();
+        let mut partial = vec![vec![to]];
+        while let Some(path) = partial.pop() {
+            let last = *path.last().unwrap();
+            if last == self.start {
+                paths.push(path.into_iter().rev().collect());
+                continue;
+            }
+
+            for &previous in self.predecessors[last].iter().filter(|previous| !path.contains(previous)) {
+                let mut longer = path.clone();
+                longer.push(previous);
+                partial.push(longer);
+            }
+        }
+        paths
+    }
+
+    /// The cells that lie on at least one shortest path to `to`, sorted.
+    pub fn on_shortest_paths(&self, to: Coord) -> Vec<Coord> {
+        if self.distance(to).is_none() {
+            return Vec::new();
+        }
+
+        let mut seen = Matrix::new(self.distances.nrows, self.distances.ncols, false);
+        let mut pending = vec![to];
+        seen[to] = true;
+
+        while let Some(coord) = pending.pop() {
+            for &previous in &self.predecessors[coord] {
+                if !seen[previous] {
+                    seen[previous] = true;
+                    pending.push(previous);
+                }
+            }
+        }
+
+        seen.enumerate()
+            .filter(|&(_, &seen)| seen)
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+}
+
+impl<T> Matrix<T> {
+    fn moves(&self, from: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::CARDINAL.into_iter().filter_map(move |direction| self.step(from, direction))
+    }
+
+    /// Breadth first search from `start`, where `passable(from, to)` allows a step.
+    pub fn bfs<F>(&self, start: Coord, mut passable: F) -> Paths
+            where F: FnMut(Coord, Coord) -> bool {
+        let mut paths = Paths::new(self, start);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(from) = queue.pop_front() {
+            let distance = paths.distances[from].unwrap() + 1;
+
+            for to in self.moves(from) {
+                if passable(from, to) && paths.relax(from, to, distance) {
+                    queue.push_back(to);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Dijkstra's algorithm from `start`, where `cost(from, to)` prices a step or forbids it.
+    pub fn dijkstra<F>(&self, start: Coord, mut cost: F) -> Paths
+            where F: FnMut(Coord, Coord) -> Option<usize> {
+        let mut paths = Paths::new(self, start);
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((distance, from))) = queue.pop() {
+            if paths.distances[from].is_some_and(|known| known < distance) {
+                continue;
+            }
+
+            for to in self.moves(from) {
+                if let Some(step) = cost(from, to) {
+                    if paths.relax(from, to, distance + step) {
+                        queue.push(Reverse((distance + step, to)));
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    /// A* search guided by the Manhattan distance, for steps costing at least 1.
+    pub fn astar<F>(&self, start: Coord, goal: Coord, mut cost: F) -> Option<(usize, Vec<Coord>)>
+            where F: FnMut(Coord, Coord) -> Option<usize> {
+        let mut best: Matrix<Option<usize>> = Matrix::new(self.nrows, self.ncols, None);
+        let mut came_from: Matrix<Option<Coord>> = Matrix::new(self.nrows, self.ncols, None);
+        let mut queue = BinaryHeap::from([Reverse((start.manhattan(goal), 0, start))]);
+        best[start] = Some(0);
+
+        while let Some(Reverse((_, distance, from))) = queue.pop() {
+            if from == goal {
+                let mut path = vec![goal];
+                while let Some(previous) = came_from[*path.last().unwrap()] {
+                    path.push(previous);
+                }
+                path.reverse();
+                return Some((distance, path));
+            }
+            if best[from].is_some_and(|known| known < distance) {
+                continue;
+            }
+
+            for to in self.moves(from) {
+                let Some(step) = cost(from, to) else { continue };
+                let next = distance + step;
+
+                if best[to].is_none_or(|known| next < known) {
+                    best[to] = Some(next);
+                    came_from[to] = Some(from);
+                    queue.push(Reverse((next + to.manhattan(goal), next, to)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Matrix<char> {
+        "\
+S..#
+.#..
+...E".parse().unwrap()
+    }
+
+    const START: Coord = Coord::new(0, 0);
+    const END: Coord = Coord::new(2, 3);
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let paths = maze.bfs(START, |_, to| maze[to] != '#');
+
+        assert_eq!(paths.distance(END), Some(5));
+        assert_eq!(paths.distance(Coord::new(0, 3)), None);
+        assert_eq!(paths.distance(Coord::new(1, 1)), None);
+
+        let path = paths.path(END).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!((path[0], path[5]), (START, END));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let maze = maze();
+        let paths = maze.bfs(START, |_, to| maze[to] != '#');
+
+        assert_eq!(paths.all_paths(END).len(), 3);
+        assert_eq!(paths.on_shortest_paths(END).len(), 10);
+        assert!(paths.all_paths(Coord::new(0, 3)).is_empty());
+        assert_eq!(paths.all_paths(START), vec![vec![START]]);
+    }
+
+    #[test]
+    fn test_all_paths_with_zero_cost_moves() {
+        let corridor: Matrix<char> = "...".parse().unwrap();
+        let paths = corridor.dijkstra(START, |_, _| Some(0));
+        let end = Coord::new(0, 2);
+
+        assert_eq!(paths.distance(end), Some(0));
+        assert_eq!(paths.all_paths(end), vec![vec![START, Coord::new(0, 1), end]]);
+        assert_eq!(paths.path(end).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let costs: Matrix<u32> = Matrix::try_parse_with("\
+131
+191
+111", |c| c.to_digit(10)).unwrap();
+        let paths = costs.dijkstra(START, |_, to| Some(costs[to] as usize));
+
+        assert_eq!(paths.distance(Coord::new(2, 2)), Some(4));
+        assert_eq!(paths.path(Coord::new(2, 2)).unwrap(), vec![
+            Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0), Coord::new(2, 1), Coord::new(2, 2),
+        ]);
+        assert_eq!(paths.distance(Coord::new(1, 1)), Some(10));
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = maze();
+        let (cost, path) = maze.astar(START, END, |_, to| (maze[to] != '#').then_some(1)).unwrap();
+
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 6);
+        assert_eq!(maze.astar(START, Coord::new(0, 3), |_, to| (maze[to] != '#').then_some(1)), None);
+    }
+
+    #[test]
+    fn test_astar_agrees_with_dijkstra() {
+        let costs: Matrix<u32> = Matrix::try_parse_with("\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581", |c| c.to_digit(10)).unwrap();
+        let goal = Coord::new(9, 9);
+        let cost = |_, to: Coord| Some(costs[to] as usize);
+
+        assert_eq!(costs.dijkstra(START, cost).distance(goal), Some(40));
+        assert_eq!(costs.astar(START, goal, cost).map(|(cost, _)| cost), Some(40));
+    }
+}
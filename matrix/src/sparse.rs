@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use crate::{Direction, Matrix};
+
+/// A (row, column) position in a [`SparseGrid`], which may be negative.
+pub type Position = (isize, isize);
+
+/// An unbounded grid that only stores the cells that have been set.
+#[derive(Debug,PartialEq,Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+    bounds: Option<(Position, Position)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid { cells: HashMap::new(), bounds: None }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads puzzle text from (0, 0), keeping the cells that `f` maps to a value.
+    pub fn parse_with<F>(text: &str, mut f: F) -> Self
+            where F: FnMut(char) -> Option<T> {
+        let mut grid = SparseGrid::new();
+
+        for (row, line) in text.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if let Some(value) = f(c) {
+                    grid.set(row as isize, col as isize, value);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        self.cells.get(&(row, col))
+    }
+
+    pub fn get_mut(&mut self, row: isize, col: isize) -> Option<&mut T> {
+        self.cells.get_mut(&(row, col))
+    }
+
+    pub fn contains(&self, row: isize, col: isize) -> bool {
+        self.cells.contains_key(&(row, col))
+    }
+
+    /// Stores `value` at (row, col), returning the value that was there before.
+    pub fn set(&mut self, row: isize, col: isize, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => ((row, col), (row, col)),
+            Some((min, max)) => ((min.0.min(row), min.1.min(col)), (max.0.max(row), max.1.max(col))),
+        });
+
+        self.cells.insert((row, col), value)
+    }
+
+    pub fn remove(&mut self, row: isize, col: isize) -> Option<T> {
+        let removed = self.cells.remove(&(row, col));
+
+        // Only a cell on the edge of the bounding box can make it shrink
+        if let Some((min, max)) = self.bounds {
+            if removed.is_some() && (row == min.0 || row == max.0 || col == min.1 || col == max.1) {
+                self.bounds = self.compute_bounds();
+            }
+        }
+        removed
+    }
+
+    fn compute_bounds(&self) -> Option<(Position, Position)> {
+        let rows = self.cells.keys().map(|&(row, _)| row);
+        let cols = self.cells.keys().map(|&(_, col)| col);
+
+        Some(((rows.clone().min()?, cols.clone().min()?), (rows.max()?, cols.max()?)))
+    }
+
+    /// The corners of the smallest rectangle holding every stored cell.
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.iter().map(|(&position, value)| (position, value))
+    }
+
+    /// The position one step away in `direction`. The grid has no edges, so there always is one.
+    pub fn step(position: Position, direction: Direction) -> Position {
+        let (drow, dcol) = direction.delta();
+
+        (position.0 + drow, position.1 + dcol)
+    }
+
+    /// The stored cells among those at each of the `offsets` from `position`.
+    pub fn neighbors<I>(&self, position: Position, offsets: I) -> impl Iterator<Item = (Position, &T)>
+            where I: IntoIterator<Item = (isize, isize)> {
+        offsets.into_iter()
+            .map(move |(drow, dcol)| (position.0 + drow, position.1 + dcol))
+            .filter_map(|next| self.cells.get(&next).map(|value| (next, value)))
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(position, Direction::CARDINAL.map(Direction::delta))
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(position, Direction::ALL.map(Direction::delta))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// A dense copy of the bounding box and the position of its top left corner.
+    pub fn to_matrix(&self) -> (Matrix<Option<T>>, Position) {
+        let Some((min, max)) = self.bounds else {
+            return (Matrix::new(0, 0, None), (0, 0));
+        };
+
+        let nrows = (max.0 - min.0) as usize + 1;
+        let ncols = (max.1 - min.1) as usize + 1;
+        let mut matrix = Matrix::new(nrows, ncols, None);
+        for (&(row, col), value) in &self.cells {
+            matrix.set((row - min.0) as usize, (col - min.1) as usize, Some(value.clone())).unwrap();
+        }
+
+        (matrix, min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_get() {
+        let mut grid = SparseGrid::new();
+
+        assert_eq!(grid.set(-5, 1_000_000, 'a'), None);
+        assert_eq!(grid.set(-5, 1_000_000, 'b'), Some('a'));
+        assert_eq!(grid.get(-5, 1_000_000), Some(&'b'));
+        assert_eq!(grid.get(0, 0), None);
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.set(2, -3, ());
+        grid.set(-1, 4, ());
+        grid.set(0, 0, ());
+        assert_eq!(grid.bounds(), Some(((-1, -3), (2, 4))));
+
+        grid.remove(0, 0);
+        assert_eq!(grid.bounds(), Some(((-1, -3), (2, 4))));
+        grid.remove(-1, 4);
+        assert_eq!(grid.bounds(), Some(((2, -3), (2, -3))));
+        grid.remove(2, -3);
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_neighbors() {
+        let grid = SparseGrid::parse_with("#..\n.#.\n..#", |c| (c == '#').then_some(()));
+
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(1, 1));
+        assert_eq!(grid.neighbors4((1, 1)).count(), 0);
+
+        let mut diagonal: Vec<Position> = grid.neighbors8((1, 1)).map(|(position, _)| position).collect();
+        diagonal.sort();
+        assert_eq!(diagonal, vec![(0, 0), (2, 2)]);
+        assert_eq!(SparseGrid::<()>::step((0, 0), Direction::UpLeft), (-1, -1));
+    }
+
+    #[test]
+    fn test_to_matrix() {
+        let mut grid = SparseGrid::new();
+        grid.set(-1, -1, 'a');
+        grid.set(0, 1, 'b');
+
+        let (matrix, origin) = grid.to_matrix();
+        assert_eq!(origin, (-1, -1));
+        assert_eq!(matrix.render(|cell| cell.unwrap_or('.')).to_string(), "a..\n..b");
+    }
+}
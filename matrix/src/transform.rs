@@ -0,0 +1,194 @@
+use std::ops::Index;
+
+use aoc_error::{Error, Result};
+
+use crate::{Coord, Matrix};
+
+impl<T: Clone> Matrix<T> {
+    /// A new `nrows` x `ncols` matrix whose cell (row, col) is copied from `source(row, col)`.
+    fn remap<F>(&self, nrows: usize, ncols: usize, source: F) -> Matrix<T>
+            where F: Fn(usize, usize) -> (usize, usize) {
+        let elements = (0..nrows)
+            .flat_map(|row| (0..ncols).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let (row, col) = source(row, col);
+                self.elements[row * self.ncols + col].clone()
+            })
+            .collect();
+
+        Matrix { elements, nrows, ncols }
+    }
+
+    /// Mirrors the matrix along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Matrix<T> {
+        self.remap(self.ncols, self.nrows, |row, col| (col, row))
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate90(&self) -> Matrix<T> {
+        self.remap(self.ncols, self.nrows, |row, col| (self.nrows - 1 - col, row))
+    }
+
+    pub fn rotate180(&self) -> Matrix<T> {
+        self.remap(self.nrows, self.ncols, |row, col| (self.nrows - 1 - row, self.ncols - 1 - col))
+    }
+
+    /// Rotates a quarter turn counterclockwise.
+    pub fn rotate270(&self) -> Matrix<T> {
+        self.remap(self.ncols, self.nrows, |row, col| (col, self.ncols - 1 - row))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_h(&self) -> Matrix<T> {
+        self.remap(self.nrows, self.ncols, |row, col| (row, self.ncols - 1 - col))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_v(&self) -> Matrix<T> {
+        self.remap(self.nrows, self.ncols, |row, col| (self.nrows - 1 - row, col))
+    }
+}
+
+/// A rectangular part of a matrix, borrowed, with coordinates relative to its top left.
+#[derive(Debug,Clone,Copy)]
+pub struct View<'a, T> {
+    matrix: &'a Matrix<T>,
+    pub origin: Coord,
+    pub nrows: usize,
+    pub ncols: usize,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if row < self.nrows && col < self.ncols {
+            self.matrix.get(self.origin.row + row, self.origin.col + col)
+        } else {
+            None
+        }
+    }
+
+    /// Row `row` of the view, which is a contiguous part of a row of the matrix.
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(row < self.nrows, "Row {} out of bounds for a view with {} rows", row, self.nrows);
+        let start = (self.origin.row + row) * self.matrix.ncols + self.origin.col;
+
+        &self.matrix.elements[start..start + self.ncols]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [T]> + '_ {
+        (0..self.nrows).map(|row| self.row(row))
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.rows().flatten()
+    }
+
+    /// The coordinate in the matrix of a coordinate in the view.
+    pub fn to_matrix_coord(&self, coord: Coord) -> Coord {
+        Coord::new(self.origin.row + coord.row, self.origin.col + coord.col)
+    }
+}
+
+impl<T: Clone> View<'_, T> {
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix {
+            elements: self.iter().cloned().collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        }
+    }
+}
+
+impl<T> Index<Coord> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord.row, coord.col).unwrap_or_else(|| {
+            panic!("{:?} out of bounds for a {}x{} view", coord, self.nrows, self.ncols)
+        })
+    }
+}
+
+impl<T> Matrix<T> {
+    /// The `nrows` x `ncols` part of the matrix whose top left corner is `origin`.
+    pub fn view(&self, origin: Coord, nrows: usize, ncols: usize) -> Result<View<'_, T>> {
+        if origin.row + nrows > self.nrows || origin.col + ncols > self.ncols {
+            return Err(Error::OutOfBounds {
+                row: origin.row + nrows.saturating_sub(1),
+                col: origin.col + ncols.saturating_sub(1),
+                nrows: self.nrows,
+                ncols: self.ncols,
+            });
+        }
+
+        Ok(View { matrix: self, origin, nrows, ncols })
+    }
+
+    /// Every `nrows` x `ncols` view that fits in the matrix, row by row of their corners.
+    pub fn windows(&self, nrows: usize, ncols: usize) -> impl Iterator<Item = View<'_, T>> {
+        let rows = (self.nrows + 1).saturating_sub(nrows);
+        let cols = (self.ncols + 1).saturating_sub(ncols);
+
+        (0..rows)
+            .flat_map(move |row| (0..cols).map(move |col| Coord::new(row, col)))
+            .map(move |origin| View { matrix: self, origin, nrows, ncols })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters;
+
+    fn text(matrix: &Matrix<char>) -> String {
+        matrix.rows().map(|row| row.collect::<String>()).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn test_transpose() {
+        assert_eq!(text(&letters(2, 3).transpose()), "ad\nbe\ncf");
+        assert_eq!(letters(2, 3).transpose().transpose(), letters(2, 3));
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(text(&letters(2, 3).rotate90()), "da\neb\nfc");
+        assert_eq!(text(&letters(2, 3).rotate180()), "fed\ncba");
+        assert_eq!(text(&letters(2, 3).rotate270()), "cf\nbe\nad");
+        assert_eq!(letters(2, 3).rotate90().rotate270(), letters(2, 3));
+        assert_eq!(letters(2, 3).rotate90().rotate90(), letters(2, 3).rotate180());
+    }
+
+    #[test]
+    fn test_flip() {
+        assert_eq!(text(&letters(2, 3).flip_h()), "cba\nfed");
+        assert_eq!(text(&letters(2, 3).flip_v()), "def\nabc");
+        assert_eq!(letters(2, 3).flip_h().flip_v(), letters(2, 3).rotate180());
+    }
+
+    #[test]
+    fn test_view() {
+        let matrix = letters(3, 4);
+        let view = matrix.view(Coord::new(1, 1), 2, 2).unwrap();
+
+        assert_eq!(view.get(0, 0), Some(&'f'));
+        assert_eq!(view[Coord::new(1, 1)], 'k');
+        assert_eq!(view.get(0, 2), None);
+        assert_eq!(view.row(1), &['j', 'k']);
+        assert_eq!(view.iter().collect::<String>(), "fgjk");
+        assert_eq!(text(&view.to_matrix()), "fg\njk");
+        assert_eq!(view.to_matrix_coord(Coord::new(1, 0)), Coord::new(2, 1));
+
+        assert!(matches!(matrix.view(Coord::new(2, 2), 2, 2), Err(Error::OutOfBounds { row: 3, col: 3, .. })));
+    }
+
+    #[test]
+    fn test_windows() {
+        let matrix = letters(3, 4);
+
+        let corners: String = matrix.windows(2, 3).map(|view| view[Coord::new(0, 0)]).collect();
+        assert_eq!(corners, "abef");
+        assert_eq!(matrix.windows(4, 1).count(), 0);
+    }
+}
@@ -1,15 +1,16 @@
 use std::fs;
 
-pub fn parse_content_by_rows(content: &str)
-        -> Result<Vec<Vec<usize>>, Box<dyn std::error::Error>> {
+use aoc_error::{split_words, Error, Result};
+
+pub fn parse_content_by_rows(content: &str) -> Result<Vec<Vec<usize>>> {
 
     let mut rows: Vec<Vec<usize>> = Vec::new();
 
-    for line in content.lines().filter(|line| !line.trim().is_empty()) {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        let items: Vec<usize> = words.iter()
-            .map(|s| s.parse::<usize>())
-            .collect::<Result<_,_>>()?;
+    for (i, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let items: Vec<usize> = split_words(line)
+            .map(|(offset, word)| word.parse::<usize>()
+                .map_err(|err| Error::parse_at(line, offset, format!("{}: {}", err, word)).offset_lines(i)))
+            .collect::<Result<_>>()?;
 
         rows.push(items);
 
@@ -17,8 +18,7 @@ pub fn parse_content_by_rows(content: &str)
     Ok(rows)
 }
 
-pub fn read_rows_from_file(filename: &str)
-        -> Result<Vec<Vec<usize>>, Box<dyn std::error::Error>> {
+pub fn read_rows_from_file(filename: &str) -> Result<Vec<Vec<usize>>> {
     let content = fs::read_to_string(filename)?;
     let result = parse_content_by_rows(&content)?;
 
@@ -30,7 +30,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_content() -> Result<(), Box<dyn std::error::Error>> {
+    fn test_parse_content() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let content =  "\
 7 6 4 2 1
 1 2 7 8 9
@@ -38,7 +38,7 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        let actual = vec![
+        let actual = [
             [7,6,4,2,1],
             [1,2,7,8,9],
             [9,7,6,2,1],
@@ -58,7 +58,7 @@ mod tests {
             Ok(())
         } else {
             let mut s: String = "".to_string();
-            let _ = rows.iter()
+            rows.iter()
                 .zip(actual.iter())
                 .for_each(|(a, b)| {
                     if a != b {
@@ -67,7 +67,14 @@ mod tests {
                 });
 
             s.pop();s.pop();
-            return Err(s.into());
+            Err(s.into())
         }
     }
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_content_by_rows("1 2 3\n\n4 x 6").unwrap_err();
+
+        assert!(matches!(err, Error::Parse { line: 3, column: 3, .. }));
+    }
 }
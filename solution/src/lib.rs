@@ -0,0 +1,130 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use aoc_error::Result;
+
+/// The answer to one part, or `None` for a part that isn't solved yet.
+pub trait Answer {
+    fn answer(&self) -> Option<String>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn answer(&self) -> Option<String> {
+                Some(self.to_string())
+            }
+        })*
+    };
+}
+
+impl_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+impl<T: Answer> Answer for Option<T> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().and_then(Answer::answer)
+    }
+}
+
+/// A solved puzzle day. The input is parsed once and shared by both parts,
+/// and each part hands its answer back as a value instead of printing it.
+pub trait Solution {
+    type Input: 'static;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(content: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// Object safe view of a [`Solution`], so that days with different input
+/// and answer types can be kept side by side in one registry.
+pub trait Solver: Sync {
+    fn parse(&self, content: &str) -> Result<Box<dyn Any>>;
+    fn part1(&self, input: &dyn Any) -> Option<String>;
+    fn part2(&self, input: &dyn Any) -> Option<String>;
+}
+
+pub struct Erased<S>(PhantomData<S>);
+
+impl<S> Erased<S> {
+    pub const fn new() -> Self {
+        Erased(PhantomData)
+    }
+}
+
+impl<S> Default for Erased<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input {
+    input.downcast_ref::<S::Input>()
+        .expect("Input was parsed by a different solution")
+}
+
+impl<S: Solution + Sync> Solver for Erased<S> {
+    fn parse(&self, content: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(content)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Option<String> {
+        S::part1(downcast::<S>(input)).answer()
+    }
+
+    fn part2(&self, input: &dyn Any) -> Option<String> {
+        S::part2(downcast::<S>(input)).answer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_error::{split_words, Error};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<usize>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(content: &str) -> Result<Self::Input> {
+            split_words(content)
+                .map(|(offset, word)| word.parse::<usize>()
+                    .map_err(|err| Error::parse_at(content, offset, err.to_string())))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Part1 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Part2 {
+            format!("{:?}", input)
+        }
+    }
+
+    static SUM: &dyn Solver = &Erased::<Sum>::new();
+
+    #[test]
+    fn test_solver() {
+        let input = SUM.parse("1 2 3").unwrap();
+
+        assert_eq!(SUM.part1(input.as_ref()), Some("6".to_string()));
+        assert_eq!(SUM.part2(input.as_ref()), Some("[1, 2, 3]".to_string()));
+    }
+
+    #[test]
+    fn test_unsolved_answer() {
+        assert_eq!(None::<usize>.answer(), None);
+        assert_eq!(Some(4usize).answer(), Some("4".to_string()));
+    }
+
+    #[test]
+    fn test_solver_parse_error() {
+        assert!(matches!(SUM.parse("1 two 3"), Err(Error::Parse { line: 1, column: 3, .. })));
+    }
+}
@@ -1,3 +1,6 @@
+use aoc_error::{Error, Result};
+use logger::{debug, trace};
+
 use crate::token::Token;
 use crate::reader::{TokenReader,NoneReader,NumReader,WordReader,CharReader,CatchAllReader};
 use crate::visitor::{TokenVisitor,MultiplicationVisitor};
@@ -8,27 +11,31 @@ pub fn read_next_token(token_readers: &Vec<Box<dyn TokenReader>>, text: &str) ->
             return reader.read_token(text);
         }
     }
-    println!("Returned without finding tokens");
+    debug!("Returned without finding tokens");
     None
 }
 
+/// Splits the whole text into tokens. Fails at the first position where no reader
+/// recognises anything, or where a reader doesn't consume any input.
 pub fn tokenize(token_readers: &Vec<Box<dyn TokenReader>>, text: &str)
-        -> Vec<Box<dyn Token>> {
+        -> Result<Vec<Box<dyn Token>>> {
     let mut i: usize = 0;
     let mut result:Vec<Box<dyn Token>> = Vec::new();
 
     while i < text.len() {
-        println!("{} of {}", i, text.len());
-        if let Some((token, j)) = read_next_token(token_readers, &text[i..]) {
-            result.push(token);
-            i += j;
-        } else {
-            break;
+        trace!("{} of {}", i, text.len());
+        match read_next_token(token_readers, &text[i..]) {
+            Some((token, j)) if j > 0 => {
+                result.push(token);
+                i += j;
+            },
+            Some((token, _)) => return Err(Error::parse_at(text, i, format!("{} doesn't consume any input", token))),
+            None => return Err(Error::parse_at(text, i, "no token found")),
         }
 
     }
 
-    result
+    Ok(result)
 }
 
 pub fn process_tokens(visitor: &mut dyn TokenVisitor, tokens: &Vec<Box<dyn Token>> ) -> usize {
@@ -39,23 +46,30 @@ pub fn process_tokens(visitor: &mut dyn TokenVisitor, tokens: &Vec<Box<dyn Token
     visitor.get_result()
 }
 
-pub fn process_text(input: &str) -> usize {
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(WordReader{word: "mul".to_string()}));
-        readers.push(Box::new(WordReader{word: "don't()".to_string()}));
-        readers.push(Box::new(WordReader{word: "do()".to_string()}));
-        readers.push(Box::new(CharReader{c: ')'}));
-        readers.push(Box::new(CharReader{c: ','}));
-        readers.push(Box::new(CharReader{c: '('}));
-        readers.push(Box::new(NumReader));
-        readers.push(Box::new(NoneReader));
-        readers.push(Box::new(CatchAllReader));
+/// Splits puzzle text into the words, numbers and punctuation of `mul(a,b)`, `do()` and
+/// `don't()` instructions, with everything else caught by the catch-all reader.
+pub fn tokenize_text(input: &str) -> Result<Vec<Box<dyn Token>>> {
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(WordReader{word: "mul".to_string()}),
+            Box::new(WordReader{word: "don't()".to_string()}),
+            Box::new(WordReader{word: "do()".to_string()}),
+            Box::new(CharReader{c: ')'}),
+            Box::new(CharReader{c: ','}),
+            Box::new(CharReader{c: '('}),
+            Box::new(NumReader),
+            Box::new(NoneReader),
+            Box::new(CatchAllReader),
+        ];
+
+        tokenize(&readers, input)
+}
 
-        let tokens = tokenize(&readers, &input);
+pub fn process_text(input: &str) -> Result<usize> {
+        let tokens = tokenize_text(input)?;
 
         let mut visitor = MultiplicationVisitor::new();
 
-        process_tokens(&mut visitor, &tokens)
+        Ok(process_tokens(&mut visitor, &tokens))
 }
 
 #[cfg(test)]
@@ -67,8 +81,9 @@ mod tests {
     fn test_none_reader() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(NoneReader));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(NoneReader),
+        ];
 
         let (token, i) = read_next_token(&readers, input).unwrap();
         assert_eq!(i, 1);
@@ -79,8 +94,9 @@ mod tests {
     fn test_mul_reader() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(WordReader{word: "mul".to_string()}));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(WordReader{word: "mul".to_string()}),
+        ];
 
         let (token, i) = read_next_token(&readers, &input[1..]).unwrap();
         assert_eq!(i, 3);
@@ -88,7 +104,7 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<WordToken>() {
             assert_eq!(concrete.word, "mul");
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
 
     }
@@ -97,8 +113,9 @@ mod tests {
     fn test_start_par_reader() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(CharReader{c: '('}));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(CharReader{c: '('}),
+        ];
 
         let (token, i) = read_next_token(&readers, &input[4..]).unwrap();
         assert_eq!(i, 1);
@@ -106,7 +123,7 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<CharToken>() {
             assert_eq!(concrete.c, '(');
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
     }
 
@@ -114,8 +131,9 @@ mod tests {
     fn test_num_reader() {
         let input = "xmul(2465,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(NumReader));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(NumReader),
+        ];
 
         let (token, i) = read_next_token(&readers, &input[5..]).unwrap();
         assert_eq!(i, 4);
@@ -123,7 +141,7 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<NumToken>() {
             assert_eq!(concrete.val, 2465);
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
     }
 
@@ -131,8 +149,9 @@ mod tests {
     fn test_comma_reader() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(CharReader{c: ','}));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(CharReader{c: ','}),
+        ];
 
         let (token, i) = read_next_token(&readers, &input[6..]).unwrap();
         assert_eq!(i, 1);
@@ -140,7 +159,7 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<CharToken>() {
             assert_eq!(concrete.c, ',');
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
     }
 
@@ -148,8 +167,9 @@ mod tests {
     fn test_end_par_reader() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(CharReader{c: ')'}));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(CharReader{c: ')'}),
+        ];
 
         let (token, i) = read_next_token(&readers, &input[8..]).unwrap();
         assert_eq!(i, 1);
@@ -157,7 +177,7 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<CharToken>() {
             assert_eq!(concrete.c, ')');
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
     }
 
@@ -165,8 +185,9 @@ mod tests {
     fn test_do_reader() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(WordReader{word: "do()".to_string()}));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(WordReader{word: "do()".to_string()}),
+        ];
 
         for (i,c) in input.chars().enumerate() {println!("{i}, {c}"); }
         let (token, i) = read_next_token(&readers, &input[59..]).unwrap();
@@ -175,7 +196,7 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<WordToken>() {
             assert_eq!(concrete.word, "do()");
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
 
     }
@@ -184,8 +205,9 @@ mod tests {
     fn test_dont_reader() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(WordReader{word: "don't()".to_string()}));
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(WordReader{word: "don't()".to_string()}),
+        ];
 
         for (i,c) in input.chars().enumerate() {println!("{i}, {c}"); }
         let (token, i) = read_next_token(&readers, &input[20..]).unwrap();
@@ -194,24 +216,25 @@ mod tests {
         if let Some(concrete) = token.as_any().downcast_ref::<WordToken>() {
             assert_eq!(concrete.word, "don't()");
         } else {
-            assert!(false);
+            panic!("Unexpected token type");
         }
     }
 
     #[test]
     fn test_tokenize() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(WordReader{word: "don't()".to_string()}));
-        readers.push(Box::new(WordReader{word: "do()".to_string()}));
-        readers.push(Box::new(CharReader{c: ')'}));
-        readers.push(Box::new(CharReader{c: ','}));
-        readers.push(Box::new(CharReader{c: '('}));
-        readers.push(Box::new(NumReader));
-        readers.push(Box::new(NoneReader));
-        readers.push(Box::new(WordReader{word: "mul".to_string()}));
-
-        let tokens = tokenize(&readers, &input);
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(WordReader{word: "don't()".to_string()}),
+            Box::new(WordReader{word: "do()".to_string()}),
+            Box::new(CharReader{c: ')'}),
+            Box::new(CharReader{c: ','}),
+            Box::new(CharReader{c: '('}),
+            Box::new(NumReader),
+            Box::new(NoneReader),
+            Box::new(WordReader{word: "mul".to_string()}),
+        ];
+
+        let tokens = tokenize(&readers, input).unwrap();
 
         for token in &tokens {
             println!("{}", token);
@@ -223,17 +246,18 @@ mod tests {
     #[test]
     fn test_process_tokens() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        let mut readers: Vec<Box<dyn TokenReader>> = Vec::new();
-        readers.push(Box::new(WordReader{word: "don't()".to_string()}));
-        readers.push(Box::new(WordReader{word: "do()".to_string()}));
-        readers.push(Box::new(CharReader{c: ')'}));
-        readers.push(Box::new(CharReader{c: ','}));
-        readers.push(Box::new(CharReader{c: '('}));
-        readers.push(Box::new(NumReader));
-        readers.push(Box::new(NoneReader));
-        readers.push(Box::new(WordReader{word: "mul".to_string()}));
-
-        let tokens = tokenize(&readers, &input);
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(WordReader{word: "don't()".to_string()}),
+            Box::new(WordReader{word: "do()".to_string()}),
+            Box::new(CharReader{c: ')'}),
+            Box::new(CharReader{c: ','}),
+            Box::new(CharReader{c: '('}),
+            Box::new(NumReader),
+            Box::new(NoneReader),
+            Box::new(WordReader{word: "mul".to_string()}),
+        ];
+
+        let tokens = tokenize(&readers, input).unwrap();
 
         for token in &tokens {
             println!("{}", token);
@@ -251,7 +275,26 @@ mod tests {
     fn test_process_text() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
-        let result = process_text(input);
+        let result = process_text(input).unwrap();
         assert_eq!(48, result);
     }
+
+    #[test]
+    fn test_process_text_non_ascii() {
+        assert_eq!(process_text("é").unwrap(), 0);
+        assert_eq!(process_text("ééémul(2,3)ü,mul(4,5)").unwrap(), 26);
+    }
+
+    #[test]
+    fn test_tokenize_error() {
+        let readers: Vec<Box<dyn TokenReader>> = vec![
+            Box::new(NumReader),
+            Box::new(CharReader{c: ','}),
+        ];
+
+        assert_eq!(tokenize(&readers, "1,2").unwrap().len(), 3);
+
+        let result = tokenize(&readers, "1,2;3");
+        assert!(matches!(result, Err(Error::Parse { line: 1, column: 4, .. })));
+    }
 }
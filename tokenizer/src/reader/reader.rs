@@ -1,3 +1,5 @@
+use logger::trace;
+
 use super::traits::TokenReader;
 
 use crate::token::{CatchAllToken, CharToken, ErrorToken, NoneToken, NumToken, Token, WordToken};
@@ -30,8 +32,8 @@ impl TokenReader for NoneReader {
                 break;
             }
         }
-        if token_string.len() > 0 {
-            println!("Found NoneToken: {}", token_string);
+        if !token_string.is_empty() {
+            trace!("Found NoneToken: {}", token_string);
             Some((Box::new(NoneToken), token_string.len()))
         } else {
             Some((Box::new(ErrorToken), 0))
@@ -42,13 +44,13 @@ impl TokenReader for NoneReader {
 pub struct CatchAllReader;
 impl TokenReader for CatchAllReader {
     fn contains_token(&self, text:&str) -> bool {
-        text.len() > 0
+        !text.is_empty()
     }
 
     fn read_token(&self, text: &str) -> Option<(Box<dyn Token>, usize)> {
-        if text.len() > 0 {
-            println!("Found CatchAllToken: {}", &text[..1]);
-            Some((Box::new(CatchAllToken), 1))
+        if let Some(c) = text.chars().next() {
+            trace!("Found CatchAllToken: {}", c);
+            Some((Box::new(CatchAllToken), c.len_utf8()))
         } else {
             Some((Box::new(ErrorToken), 0))
         }
@@ -61,18 +63,12 @@ pub struct WordReader {
 
 impl TokenReader for WordReader {
     fn contains_token(&self, text:&str) -> bool {
-        let mut result = false;
-        if text.len() >= self.word.len() {
-            result = &text[..self.word.len()] == self.word;
-        }
-        if text.len() >= self.word.len() {
-        }
-        result
+        text.starts_with(&self.word)
     }
 
     fn read_token(&self, text: &str) -> Option<(Box<dyn Token>, usize)> {
-        if &text[..self.word.len()] == self.word {
-            println!("Found WordToken: {}", &text[..self.word.len()]);
+        if text.starts_with(&self.word) {
+            trace!("Found WordToken: {}", self.word);
             Some((Box::new(WordToken{word: self.word.clone()}), self.word.len()))
         } else {
             Some((Box::new(ErrorToken), 0))
@@ -86,17 +82,13 @@ pub struct CharReader {
 
 impl TokenReader for CharReader {
     fn contains_token(&self, text:&str) -> bool {
-        if text.len() > 0 {
-            &text[..1] == self.c.to_string()
-        } else {
-            false
-        }
+        text.starts_with(self.c)
     }
 
     fn read_token(&self, text: &str) -> Option<(Box<dyn Token>, usize)> {
-        if &text[..1] == self.c.to_string() {
-            println!("Found CharToken: {}", &text[..1]);
-            Some((Box::new(CharToken{c: self.c}), 1))
+        if text.starts_with(self.c) {
+            trace!("Found CharToken: {}", self.c);
+            Some((Box::new(CharToken{c: self.c}), self.c.len_utf8()))
         } else {
             Some((Box::new(ErrorToken), 0))
         }
@@ -123,7 +115,7 @@ impl TokenReader for NumReader {
         }
 
         if let Ok(res) = s.parse::<usize>() {
-            println!("Found NumToken: {}", res);
+            trace!("Found NumToken: {}", res);
             Some((Box::new(NumToken{val: res}), s.len()))
         } else {
             Some((Box::new(ErrorToken), 0))
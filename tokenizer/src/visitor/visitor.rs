@@ -1,3 +1,5 @@
+use logger::{debug, trace, warn};
+
 use super::traits::TokenVisitor;
 
 use crate::token::{CatchAllToken,ErrorToken,NoneToken,NumToken,WordToken,CharToken};
@@ -8,6 +10,12 @@ pub struct Multiplication {
     right: usize,
 }
 
+impl Default for Multiplication {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Multiplication {
     pub fn new() -> Self {
         Self { enabled: true, left: 0, right: 0 }
@@ -29,12 +37,12 @@ impl Multiplication {
     }
 
     pub fn enable (&mut self) {
-        println!("Enabled");
+        debug!("Enabled");
         self.enabled = true;
     }
 
     pub fn disable (&mut self) {
-        println!("Disabled");
+        debug!("Disabled");
         self.enabled = false;
     }
 }
@@ -45,6 +53,12 @@ pub struct MultiplicationVisitor {
     op: Multiplication,
 }
 
+impl Default for MultiplicationVisitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MultiplicationVisitor {
     pub fn new() -> Self {
         Self { depth: 0, result: 0, op: Multiplication::new() }
@@ -57,74 +71,68 @@ impl TokenVisitor for MultiplicationVisitor {
     }
 
     fn visit_catchall_token(&mut self, token: &CatchAllToken) {
-//        println!("Visiting: {}", token);
+        trace!("Visiting: {}", token);
     }
 
     fn visit_error_token(&mut self, token: &ErrorToken) {
-        println!("Visiting: {}", token);
+        debug!("Visiting: {}", token);
     }
 
     fn visit_none_token(&mut self, token: &NoneToken) {
-//        println!("Visiting: {}", token);
+        trace!("Visiting: {}", token);
         self.depth = 0;
     }
 
     fn visit_num_token(&mut self, token: &NumToken) {
-//        println!("Visiting: {}", token);
+        trace!("Visiting: {}", token);
 
         if self.depth == 2 {
             self.depth += 1;
-            print!("{}", token.val);
             self.op.update_left(token.val);
         } else if self.depth == 4 {
             self.depth += 1;
             self.op.update_right(token.val);
-            print!("{}", token.val);
         } else {
             self.depth = 0;
         }
     }
 
     fn visit_word_token(&mut self, token: &WordToken) {
-//        println!("Visiting: {}", token);
+        trace!("Visiting: {}", token);
         if self.depth > 0 {
-//            println!("Stack is not empty, cleaning up!");
+            trace!("Stack is not empty, cleaning up!");
             self.depth = 0;
         }
 
         match token.word.as_str() {
             "do()" => self.op.enable(),
             "don't()" => self.op.disable(),
-            "mul" => {
-                self.depth += 1;
-                print!("mul"); },
-            _ => println!("Unknown token value: {}", token.word),
+            "mul" => self.depth += 1,
+            _ => warn!("Unknown token value: {}", token.word),
 
         }
     }
 
     fn visit_char_token(&mut self, token: &CharToken) {
- //       println!("Visiting: {}", token);
-        
+        trace!("Visiting: {}", token);
+
         match token.c{
             '(' =>  if self.depth == 1 {
-                        print!("(");
                         self.depth +=1;
                     } else { self.depth = 0; },
             ',' =>  if self.depth == 3 {
-                        print!(",");
                         self.depth += 1;
                     } else { self.depth = 0; },
             ')' =>  {
                         if self.depth == 5 {
+                            let (left, right) = (self.op.left, self.op.right);
                             let result = self.op.get_result();
-                            print!(") = {result}");
                             self.result += result;
-                            println!(" => {}", self.result);
+                            debug!("mul({left},{right}) = {result} => {}", self.result);
                         }
                         self.depth = 0;
                     },
-            _ => { self.depth = 0; println!("Unknown token value: {}", token.c)},
+            _ => { self.depth = 0; warn!("Unknown token value: {}", token.c)},
         }
     }
 }